@@ -8,6 +8,8 @@ var filesToCache = [
   "pico.orange.min.css",
   "rustedlesspass-web.js",
   "rustedlesspass-web_bg.wasm",
+  "password_worker.js",
+  "password_worker_bg.wasm",
 ];
 
 // Add all files in the "assets" directory
@@ -0,0 +1,626 @@
+/*
+    This binary provides a command-line interface to the RustedLessPass password generator.
+
+    The master password is read from the terminal without echo, or from the standard input
+    when it is not a terminal. The generated password is printed on the standard output (or
+    copied to the clipboard), and the fingerprint of the master password on the standard error.
+
+    For the same inputs, the generated password is identical to the one of the web application.
+*/
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use rustedlesspass_core::aliases::Aliases;
+use rustedlesspass_core::domain::normalize_site;
+use rustedlesspass_core::fingerprintgen::{fingerprint_calculate, fingerprint_text, icon_emoji};
+use rustedlesspass_core::import_export::import_profiles;
+use rustedlesspass_core::mnemonic::MNEMONIC_WORDS;
+use rustedlesspass_core::passgen::{
+    generate_password, Algorithm, Mode, PasswordOptions, DEFAULT_ITERATIONS,
+};
+use rustedlesspass_core::passphrase::{DEFAULT_SEPARATOR, DEFAULT_WORDS, MAX_WORDS};
+use rustedlesspass_core::policy::{
+    generate_password_with_policy, preset, Policy, PolicyError, PRESETS,
+};
+use rustedlesspass_core::sshkey::derive_ssh_key;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "A Stateless Password Manager")]
+struct Cli {
+    /// Website the password is generated for
+    site: String,
+
+    /// Login (username or email address) used on the website
+    login: String,
+
+    /// Normalize the site to its registrable domain, e.g. "https://www.example.co.uk/login" to "example.co.uk"
+    #[arg(long)]
+    normalize_domain: bool,
+
+    /// Length of the generated password
+    #[arg(short, long, default_value_t = 16, value_parser = clap::value_parser!(u64).range(1..=35))]
+    length: u64,
+
+    /// Counter, incremented to renew the password of a website
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    counter: u32,
+
+    /// Exclude lowercase letters (a-z)
+    #[arg(long)]
+    no_lowercase: bool,
+
+    /// Exclude uppercase letters (A-Z)
+    #[arg(long)]
+    no_uppercase: bool,
+
+    /// Exclude digits (0-9)
+    #[arg(long, alias = "no-numbers")]
+    no_digits: bool,
+
+    /// Exclude symbols (%!@)
+    #[arg(long)]
+    no_symbols: bool,
+
+    /// LessPass algorithm version, 1 for legacy LessPass v1 passwords (password output, key
+    /// derivation and characters of LessPass v1 only)
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..=2),
+        conflicts_with_all = ["passphrase", "pin", "mnemonic", "ssh_key", "algorithm", "iterations",
+            "exclude", "allowed_symbols", "no_lookalikes"])]
+    lesspass_version: u32,
+
+    /// Generate a passphrase of words instead of a password
+    #[arg(long)]
+    passphrase: bool,
+
+    /// Generate a PIN code of 4 to 12 digits (set with --length) avoiding weak patterns
+    #[arg(long, conflicts_with = "passphrase")]
+    pin: bool,
+
+    /// Generate a BIP-39 mnemonic of 12 or 24 words instead of a password
+    #[arg(long, value_name = "WORDS", value_parser = parse_mnemonic_words, conflicts_with_all = ["passphrase", "pin"])]
+    mnemonic: Option<usize>,
+
+    /// Number of words of the passphrase
+    #[arg(long, default_value_t = DEFAULT_WORDS as u64, value_parser = clap::value_parser!(u64).range(1..=MAX_WORDS as u64))]
+    words: u64,
+
+    /// Separator between the words of the passphrase
+    #[arg(long, default_value = DEFAULT_SEPARATOR, allow_hyphen_values = true)]
+    separator: String,
+
+    /// Capitalize the words of the passphrase
+    #[arg(long)]
+    capitalize: bool,
+
+    /// Characters never used in the generated password, e.g. "<>'\""
+    #[arg(long, default_value = "", allow_hyphen_values = true)]
+    exclude: String,
+
+    /// Symbols allowed in the generated password, instead of every ASCII symbol
+    #[arg(long, allow_hyphen_values = true)]
+    allowed_symbols: Option<String>,
+
+    /// Never use characters that look alike, such as 0, O, 1, l, I and |
+    #[arg(long)]
+    no_lookalikes: bool,
+
+    /// Policy preset the password must match (bank, mainframe or no-repeats)
+    #[arg(long, value_parser = parse_policy, conflicts_with_all = ["passphrase", "mnemonic"])]
+    policy: Option<Policy>,
+
+    /// PBKDF2 digest used to derive the password (sha256, sha384 or sha512)
+    #[arg(long, default_value_t = Algorithm::Sha256)]
+    algorithm: Algorithm,
+
+    /// PBKDF2 iteration count
+    #[arg(long, default_value_t = DEFAULT_ITERATIONS, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Generate an Ed25519 SSH key for the site as host, printing the OpenSSH private key
+    /// instead of a password and the public key on the standard error
+    #[arg(long, conflicts_with_all = ["passphrase", "pin", "mnemonic", "policy"])]
+    ssh_key: bool,
+
+    /// Profiles export of the web application, plain or encrypted with the master password,
+    /// whose site aliases apply to the site
+    #[arg(long, value_name = "FILE")]
+    aliases: Option<PathBuf>,
+
+    /// Copy the generated password to the clipboard instead of printing it
+    #[cfg(feature = "clipboard")]
+    #[arg(long)]
+    copy: bool,
+}
+
+impl Cli {
+    /*
+        Builds the password generation options from the command-line arguments.
+
+        Arguments:
+        - `master_password`: A string slice representing the master password.
+
+        Returns:
+        A `PasswordOptions` struct, or `None` if every character set is excluded from a
+        password or a PIN.
+    */
+    fn password_options(&self, master_password: &str) -> Option<PasswordOptions> {
+        // Passphrases, mnemonics and SSH keys do not use the character sets
+        let uses_character_sets = !self.passphrase && self.mnemonic.is_none() && !self.ssh_key;
+        if uses_character_sets
+            && self.no_lowercase
+            && self.no_uppercase
+            && self.no_digits
+            && self.no_symbols
+        {
+            return None;
+        }
+
+        Some(PasswordOptions {
+            domain: if self.normalize_domain {
+                normalize_site(&self.site)
+            } else {
+                self.site.clone()
+            },
+            login: self.login.clone(),
+            master_password: master_password.to_string(),
+            lowercase: !self.no_lowercase,
+            uppercase: !self.no_uppercase,
+            digits: !self.no_digits,
+            symbols: !self.no_symbols,
+            length: self.length as usize,
+            counter: self.counter,
+            version: self.lesspass_version,
+            algorithm: self.algorithm,
+            iterations: self.iterations,
+            excluded_characters: self.exclude.clone(),
+            allowed_symbols: self.allowed_symbols.clone(),
+            no_lookalikes: self.no_lookalikes,
+            sub_counter: 0,
+            mode: if self.passphrase {
+                Mode::Passphrase {
+                    words: self.words as usize,
+                    separator: self.separator.clone(),
+                    capitalize: self.capitalize,
+                }
+            } else if self.pin {
+                Mode::Pin
+            } else if let Some(words) = self.mnemonic {
+                Mode::Mnemonic { words }
+            } else {
+                Mode::Password
+            },
+        })
+    }
+}
+
+// Parses the name of a policy preset
+fn parse_policy(name: &str) -> Result<Policy, String> {
+    preset(name).cloned().ok_or_else(|| {
+        let names: Vec<&str> = PRESETS.iter().map(|(name, _)| *name).collect();
+        format!("unknown policy, expected one of: {}", names.join(", "))
+    })
+}
+
+// Parses the number of words of a mnemonic
+fn parse_mnemonic_words(words: &str) -> Result<usize, String> {
+    words
+        .parse()
+        .ok()
+        .filter(|words| MNEMONIC_WORDS.contains(words))
+        .ok_or_else(|| "a mnemonic has 12 or 24 words".to_string())
+}
+
+// Generates the password, matching the policy if one was given
+fn generate(cli: &Cli, options: PasswordOptions) -> Result<String, PolicyError> {
+    match &cli.policy {
+        Some(policy) => {
+            generate_password_with_policy(options, policy).map(|(password, _)| password)
+        }
+        None => Ok(generate_password(options)),
+    }
+}
+
+/*
+    Parses the site aliases of a profiles export of the web application.
+
+    Arguments:
+    - `contents`: A string slice representing the export, plain or encrypted.
+    - `master_password`: A string slice representing the master password decrypting it.
+
+    Returns:
+    The `Aliases` of the export, or the error of the import.
+*/
+fn parse_aliases(contents: &str, master_password: &str) -> Result<Aliases, String> {
+    let import = import_profiles(contents, master_password).map_err(|e| e.to_string())?;
+    let mut aliases = Aliases::default();
+    for alias in &import.aliases {
+        aliases.add(&alias.alias, &alias.site);
+    }
+    Ok(aliases)
+}
+
+// Reads the master password from the terminal without echo, or from the standard input
+fn read_master_password() -> io::Result<String> {
+    if io::stdin().is_terminal() {
+        rpassword::prompt_password("Master password: ")
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+// Formats the fingerprint icons as words and emoji, e.g. "car, hashtag, bug (🚗 #️⃣ 🐛)"
+fn fingerprint_names(master_password: &str) -> String {
+    let icons = fingerprint_calculate(master_password);
+    let emoji: Vec<&str> = icons.iter().filter_map(|icon| icon_emoji(icon)).collect();
+    format!("{} ({})", fingerprint_text(&icons), emoji.join(" "))
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(password: &str) -> Result<(), arboard::Error> {
+    arboard::Clipboard::new()?.set_text(password)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let master_password = match read_master_password() {
+        Ok(master_password) => master_password,
+        Err(error) => {
+            eprintln!("error: unable to read the master password: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(mut options) = cli.password_options(&master_password) else {
+        eprintln!("error: at least one character set must be enabled");
+        return ExitCode::FAILURE;
+    };
+
+    if let Some(path) = &cli.aliases {
+        let aliases = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| parse_aliases(&contents, &master_password));
+        match aliases {
+            Ok(aliases) => options.domain = aliases.resolve(&options.domain).to_string(),
+            Err(error) => {
+                eprintln!("error: unable to read the aliases: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    eprintln!("Fingerprint: {}", fingerprint_names(&master_password));
+    if cli.ssh_key {
+        let key = derive_ssh_key(&options);
+        eprintln!("Public key: {}", key.public_key);
+        print!("{}", key.private_key);
+        return ExitCode::SUCCESS;
+    }
+
+    let password = match generate(&cli, options) {
+        Ok(password) => password,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    #[cfg(feature = "clipboard")]
+    if cli.copy {
+        return match copy_to_clipboard(&password) {
+            Ok(()) => {
+                eprintln!("Password copied to the clipboard");
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("error: unable to copy the password: {}", error);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    println!("{}", password);
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustedlesspass_core::import_export::export_profiles;
+    use rustedlesspass_core::profiles::Profiles;
+
+    #[test]
+    fn test_default_options_match_web_form() {
+        let cli = Cli::parse_from(["rustedlesspass-cli", "lorem ipsum", "lorem ipsum"]);
+
+        let password = generate_password(cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(password, "fV1^3lS*'[knImg8");
+    }
+
+    #[test]
+    fn test_normalize_domain_flag() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "https://www.example.org/login",
+            "contact@example.org",
+            "--normalize-domain",
+        ]);
+
+        let options = cli.password_options("password").unwrap();
+
+        assert_eq!(options.domain, "example.org");
+        assert_eq!(generate_password(options), "WHLpUL)e00[iHR+w");
+    }
+
+    #[test]
+    fn test_aliases_file() {
+        let mut aliases = Aliases::default();
+        aliases.add("example.net", "example.com");
+        let export = export_profiles(&Profiles::default(), &aliases);
+        let cli = Cli::parse_from(["rustedlesspass-cli", "https://www.example.net/", "login"]);
+
+        let aliases = parse_aliases(&export, "password").unwrap();
+        let options = cli.password_options("password").unwrap();
+
+        // The alias applies whether or not the site is normalized
+        assert_eq!(aliases.resolve(&options.domain), "example.com");
+        assert!(parse_aliases("{}", "password").is_err());
+    }
+
+    #[test]
+    fn test_character_set_flags() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "site",
+            "login",
+            "--length",
+            "20",
+            "--counter",
+            "3",
+            "--no-symbols",
+        ]);
+
+        let options = cli.password_options("master").unwrap();
+
+        assert!(options.lowercase && options.uppercase && options.digits);
+        assert!(!options.symbols);
+        assert_eq!(options.length, 20);
+        assert_eq!(options.counter, 3);
+    }
+
+    #[test]
+    fn test_lesspass_version_1() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "example.org",
+            "contact@example.org",
+            "--length",
+            "12",
+            "--lesspass-version",
+            "1",
+        ]);
+
+        let password = generate_password(cli.password_options("password").unwrap());
+
+        assert_eq!(password, "izIS5@ozYM2?");
+    }
+
+    #[test]
+    fn test_lesspass_version_1_rejects_other_outputs() {
+        for output in [
+            &["--pin"][..],
+            &["--passphrase"],
+            &["--mnemonic", "12"],
+            &["--ssh-key"],
+            &["--algorithm", "sha512"],
+            &["--iterations", "1000"],
+            &["--exclude", "<>"],
+            &["--allowed-symbols", "!@"],
+            &["--no-lookalikes"],
+        ] {
+            let args = ["rustedlesspass-cli", "a", "b", "--lesspass-version", "1"];
+            let error = Cli::try_parse_from(args.iter().chain(output)).unwrap_err();
+            assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+    }
+
+    #[test]
+    fn test_kdf_flags() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "lorem ipsum",
+            "lorem ipsum",
+            "--algorithm",
+            "sha512",
+        ]);
+
+        let password = generate_password(cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(password, "^aqiM!GwRqy9r7&~");
+        assert!(
+            Cli::try_parse_from(["rustedlesspass-cli", "a", "b", "--algorithm", "md5"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_custom_character_flags() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "example.org",
+            "contact@example.org",
+            "--allowed-symbols",
+            "-_",
+        ]);
+
+        let password = generate_password(cli.password_options("password").unwrap());
+
+        assert_eq!(password, "ES-XSsZQ_9bGf7D6");
+    }
+
+    #[test]
+    fn test_no_lookalikes_flag() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "example.org",
+            "contact@example.org",
+            "--no-lookalikes",
+        ]);
+
+        let password = generate_password(cli.password_options("password").unwrap());
+
+        assert_eq!(password, "zzK7$/&\"<Yd#JEEA");
+    }
+
+    #[test]
+    fn test_policy_flag() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "lorem ipsum",
+            "lorem ipsum",
+            "--counter",
+            "2",
+            "--policy",
+            "bank",
+        ]);
+
+        let password = generate(&cli, cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(password, Ok("h$Q{Up\"IWKB1Gewm".to_string()));
+        assert!(Cli::try_parse_from(["rustedlesspass-cli", "a", "b", "--policy", "x"]).is_err());
+        assert!(Cli::try_parse_from([
+            "rustedlesspass-cli",
+            "a",
+            "b",
+            "--policy",
+            "bank",
+            "--passphrase"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_passphrase_flags() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "lorem ipsum",
+            "lorem ipsum",
+            "--passphrase",
+            "--words",
+            "3",
+            "--separator",
+            " ",
+        ]);
+
+        let passphrase = generate_password(cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(passphrase, "carport sanding crazed");
+    }
+
+    #[test]
+    fn test_pin_flag() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "lorem ipsum",
+            "lorem ipsum",
+            "--pin",
+            "--length",
+            "4",
+        ]);
+
+        let pin = generate_password(cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(pin, "0796");
+        assert!(
+            Cli::try_parse_from(["rustedlesspass-cli", "a", "b", "--pin", "--passphrase"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_mnemonic_flag() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "lorem ipsum",
+            "lorem ipsum",
+            "--mnemonic",
+            "12",
+        ]);
+
+        let mnemonic = generate_password(cli.password_options("lorem ipsum").unwrap());
+
+        assert_eq!(
+            mnemonic,
+            "call blood funny town dash peanut worry demise change tail border mushroom"
+        );
+        assert!(Cli::try_parse_from(["rustedlesspass-cli", "a", "b", "--mnemonic", "18"]).is_err());
+    }
+
+    #[test]
+    fn test_ssh_key_flag() {
+        let cli = Cli::parse_from(["rustedlesspass-cli", "example.org", "git", "--ssh-key"]);
+
+        let key = derive_ssh_key(&cli.password_options("password").unwrap());
+
+        assert_eq!(
+            key.public_key,
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGDrSFdC6uSZ2mZ4Y5kkbCr8GQZ6BZUDrvBM/dhIh5Z/ git@example.org"
+        );
+        assert!(
+            Cli::try_parse_from(["rustedlesspass-cli", "a", "b", "--ssh-key", "--pin"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "rustedlesspass-cli",
+            "a",
+            "b",
+            "--ssh-key",
+            "--mnemonic",
+            "12"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_every_character_set_excluded() {
+        let cli = Cli::parse_from([
+            "rustedlesspass-cli",
+            "site",
+            "login",
+            "--no-lowercase",
+            "--no-uppercase",
+            "--no-digits",
+            "--no-symbols",
+        ]);
+
+        assert!(cli.password_options("master").is_none());
+    }
+
+    #[test]
+    fn test_character_sets_ignored_without_characters() {
+        let excluded = [
+            "--no-lowercase",
+            "--no-uppercase",
+            "--no-digits",
+            "--no-symbols",
+        ];
+        for output in [&["--passphrase"][..], &["--mnemonic", "12"], &["--ssh-key"]] {
+            let args = ["rustedlesspass-cli", "site", "login"];
+            let cli = Cli::parse_from(args.iter().chain(&excluded).chain(output));
+
+            assert!(cli.password_options("master").is_some());
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_names() {
+        assert_eq!(
+            fingerprint_names("lorem ipsum"),
+            "car, hashtag, bug (🚗 #️⃣ 🐛)"
+        );
+    }
+}
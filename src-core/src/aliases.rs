@@ -0,0 +1,191 @@
+/*
+   This module defines an Aliases table that maps several websites to one canonical
+   site, so that every domain of a single sign-on generates the same password.
+
+   An alias always points to a canonical site, never to another alias: adding an alias
+   to a site that is itself an alias points it to the canonical site instead, and the
+   aliases of a site that becomes an alias are moved to its new canonical site.
+
+   Sites are keyed by their registrable domain, as given by `normalize_site`, whatever the
+   domain normalization setting says, so that an alias keeps matching when it is toggled.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::normalize_site;
+
+// Define a struct to hold a website and the canonical site it stands for
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Alias {
+    pub alias: String,
+    pub site: String,
+}
+
+// Define a struct to hold every alias
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Aliases {
+    pub entries: Vec<Alias>,
+}
+
+fn key(site: &str) -> String {
+    normalize_site(site)
+}
+
+impl Aliases {
+    /*
+        Resolves a website to its canonical site.
+
+        Arguments:
+        - `site`: A string slice representing the website.
+
+        Returns:
+        The canonical site if the website is an alias, otherwise the website itself.
+    */
+    pub fn resolve<'a>(&'a self, site: &'a str) -> &'a str {
+        let site_key = key(site);
+        self.entries
+            .iter()
+            .find(|entry| entry.alias == site_key)
+            .map_or(site, |entry| entry.site.as_str())
+    }
+
+    /*
+        Maps a website to a canonical site, replacing any previous alias of the website.
+
+        Arguments:
+        - `alias`: A string slice representing the website to map.
+        - `site`: A string slice representing the canonical site.
+
+        Returns:
+        `false` if either site is empty or if the alias would map a site to itself.
+    */
+    pub fn add(&mut self, alias: &str, site: &str) -> bool {
+        let alias = key(alias);
+        let site = key(self.resolve(site));
+        if alias.is_empty() || site.is_empty() || alias == site {
+            return false;
+        }
+
+        self.entries.retain(|entry| entry.alias != alias);
+        for entry in self.entries.iter_mut().filter(|entry| entry.site == alias) {
+            entry.site = site.clone();
+        }
+        self.entries.push(Alias { alias, site });
+        true
+    }
+
+    /*
+        Removes the alias of a website.
+
+        Returns:
+        `true` if an alias was removed.
+    */
+    pub fn remove(&mut self, alias: &str) -> bool {
+        let alias = key(alias);
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.alias != alias);
+        self.entries.len() != len
+    }
+
+    /*
+        Groups the aliases by canonical site.
+
+        Returns:
+        A vector of the canonical sites with their aliases, in the order they were added.
+    */
+    pub fn groups(&self) -> Vec<(&str, Vec<&str>)> {
+        let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+        for entry in &self.entries {
+            match groups.iter_mut().find(|(site, _)| *site == entry.site) {
+                Some((_, aliases)) => aliases.push(&entry.alias),
+                None => groups.push((&entry.site, vec![&entry.alias])),
+            }
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sso() -> Aliases {
+        let mut aliases = Aliases::default();
+        assert!(aliases.add("https://login.example-sso.com/auth", "www.example.com"));
+        assert!(aliases.add("example.net", "example.com"));
+        aliases
+    }
+
+    #[test]
+    fn test_resolve() {
+        let aliases = sso();
+
+        assert_eq!(aliases.resolve("example-sso.com"), "example.com");
+        assert_eq!(aliases.resolve(" Accounts.Example-SSO.com "), "example.com");
+        assert_eq!(
+            aliases.resolve("https://mail.example.net/inbox"),
+            "example.com"
+        );
+        assert_eq!(aliases.resolve("example.com"), "example.com");
+        assert_eq!(aliases.resolve("example.org"), "example.org");
+    }
+
+    #[test]
+    fn test_resolve_keeps_sites_without_alias() {
+        let aliases = sso();
+
+        // The site is only normalized when the setting says so, which is up to the caller
+        assert_eq!(
+            aliases.resolve("https://www.example.org/login"),
+            "https://www.example.org/login"
+        );
+    }
+
+    #[test]
+    fn test_add_rejects_invalid_aliases() {
+        let mut aliases = sso();
+
+        assert!(!aliases.add("", "example.com"));
+        assert!(!aliases.add("example.org", " "));
+        assert!(!aliases.add("example.com", "example.net"));
+        assert!(!aliases.add("www.example.org", "example.org"));
+        assert_eq!(aliases, sso());
+    }
+
+    #[test]
+    fn test_add_keeps_a_single_level() {
+        let mut aliases = sso();
+
+        // An alias of an alias points to the canonical site
+        assert!(aliases.add("example.org", "example.net"));
+        assert_eq!(aliases.resolve("example.org"), "example.com");
+
+        // The aliases of a site that becomes an alias follow it
+        assert!(aliases.add("example.com", "example.info"));
+        assert_eq!(aliases.resolve("example.net"), "example.info");
+        assert_eq!(aliases.resolve("example.org"), "example.info");
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut aliases = sso();
+
+        assert!(aliases.remove("LOGIN.example-sso.com"));
+        assert!(!aliases.remove("example-sso.com"));
+        assert_eq!(aliases.resolve("example-sso.com"), "example-sso.com");
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut aliases = sso();
+        aliases.add("example.info", "example.co.uk");
+
+        assert_eq!(
+            aliases.groups(),
+            vec![
+                ("example.com", vec!["example-sso.com", "example.net"]),
+                ("example.co.uk", vec!["example.info"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,573 @@
+/*
+    This module contains functions to calculate a fingerprint based on an input string and retrieve corresponding icons using SHA256 hashing.
+
+    The `get_icon` function retrieves an icon based on a provided SHA256 hash.
+    The `fingerprint_calculate` function calculates a fingerprint based on the input string and returns corresponding icons.
+    The `fingerprint_rich` function calculates a richer fingerprint of three or four colored icons, taken from
+    a table of 64 distinct icons and the color palette of LessPass. The legacy fingerprint is left unchanged.
+    The `icon_name` and `icon_emoji` functions describe an icon with a word and an emoji, and
+    `fingerprint_text` describes a whole fingerprint, for screen readers and text-only output.
+
+    The code also includes unit tests for both empty and non-empty input cases, and for the distribution of
+    the rich fingerprint.
+*/
+
+use lesspass::get_fingerprint;
+use serde::Serialize;
+use std::fmt::Write;
+
+pub const RICH_SLOTS: usize = 3;
+pub const MAX_RICH_SLOTS: usize = 4;
+
+// Bytes of the fingerprint hash used by every slot of the rich fingerprint: 3 for the icon, 3 for the color
+const SLOT_BYTES: usize = 6;
+
+// Distinct icons of the rich fingerprint; 64 divides 2^24, so every icon is equally likely
+const RICH_ICONS: [&str; 64] = [
+    "fa-hashtag",
+    "fa-heart",
+    "fa-hotel",
+    "fa-building-columns",
+    "fa-plug",
+    "fa-truck-medical",
+    "fa-bus",
+    "fa-car",
+    "fa-plane",
+    "fa-rocket",
+    "fa-ship",
+    "fa-train-subway",
+    "fa-truck",
+    "fa-yen-sign",
+    "fa-euro-sign",
+    "fa-bitcoin-sign",
+    "fa-dollar-sign",
+    "fa-sterling-sign",
+    "fa-box-archive",
+    "fa-chart-area",
+    "fa-bed",
+    "fa-beer-mug-empty",
+    "fa-bell",
+    "fa-binoculars",
+    "fa-cake-candles",
+    "fa-bomb",
+    "fa-briefcase",
+    "fa-bug",
+    "fa-camera",
+    "fa-cart-plus",
+    "fa-certificate",
+    "fa-mug-saucer",
+    "fa-cloud",
+    "fa-comment",
+    "fa-cube",
+    "fa-utensils",
+    "fa-database",
+    "fa-gem",
+    "fa-circle-exclamation",
+    "fa-eye",
+    "fa-flag",
+    "fa-flask",
+    "fa-futbol",
+    "fa-gamepad",
+    "fa-graduation-cap",
+    "fa-anchor",
+    "fa-apple-whole",
+    "fa-bicycle",
+    "fa-book",
+    "fa-crown",
+    "fa-dragon",
+    "fa-feather",
+    "fa-fish",
+    "fa-guitar",
+    "fa-key",
+    "fa-leaf",
+    "fa-lemon",
+    "fa-moon",
+    "fa-music",
+    "fa-paw",
+    "fa-star",
+    "fa-sun",
+    "fa-tree",
+    "fa-umbrella",
+];
+
+// Icons shown while no input is typed
+const PLACEHOLDER_ICONS: [&str; 3] = ["fa-heart", "fa-brands fa-rust", "fa-rocket"];
+
+// Colors of the LessPass fingerprint, with gray instead of black to stay visible on the dark theme
+const COLORS: [&str; 14] = [
+    "#8F8F8F", "#074750", "#009191", "#FF6CB6", "#FFB5DA", "#490092", "#006CDB", "#B66DFF",
+    "#6DB5FE", "#B5DAFE", "#920000", "#924900", "#DB6D00", "#24FE23",
+];
+
+// Words and emoji describing every icon, from the legacy, rich and placeholder tables
+const ICON_LABELS: [(&str, &str, &str); 81] = [
+    ("fa-hashtag", "hashtag", "#️⃣"),
+    ("fa-heart", "heart", "❤️"),
+    ("fa-hotel", "hotel", "🏨"),
+    ("fa-university", "university", "🏛️"),
+    ("fa-building-columns", "university", "🏛️"),
+    ("fa-plug", "plug", "🔌"),
+    ("fa-ambulance", "ambulance", "🚑"),
+    ("fa-truck-medical", "ambulance", "🚑"),
+    ("fa-bus", "bus", "🚌"),
+    ("fa-car", "car", "🚗"),
+    ("fa-plane", "plane", "✈️"),
+    ("fa-rocket", "rocket", "🚀"),
+    ("fa-ship", "ship", "🚢"),
+    ("fa-subway", "subway", "🚇"),
+    ("fa-train-subway", "subway", "🚇"),
+    ("fa-truck", "truck", "🚚"),
+    ("fa-jpy", "yen", "💴"),
+    ("fa-yen-sign", "yen", "💴"),
+    ("fa-eur", "euro", "💶"),
+    ("fa-euro-sign", "euro", "💶"),
+    ("fa-brands fa-btc", "bitcoin", "🪙"),
+    ("fa-bitcoin-sign", "bitcoin", "🪙"),
+    ("fa-usd", "dollar", "💵"),
+    ("fa-dollar-sign", "dollar", "💵"),
+    ("fa-gbp", "pound", "💷"),
+    ("fa-sterling-sign", "pound", "💷"),
+    ("fa-archive", "archive", "🗄️"),
+    ("fa-box-archive", "archive", "🗄️"),
+    ("fa-area-chart", "chart", "📈"),
+    ("fa-chart-area", "chart", "📈"),
+    ("fa-bed", "bed", "🛏️"),
+    ("fa-beer", "beer", "🍺"),
+    ("fa-beer-mug-empty", "beer", "🍺"),
+    ("fa-bell", "bell", "🔔"),
+    ("fa-binoculars", "binoculars", "🔭"),
+    ("fa-birthday-cake", "cake", "🎂"),
+    ("fa-cake-candles", "cake", "🎂"),
+    ("fa-bomb", "bomb", "💣"),
+    ("fa-briefcase", "briefcase", "💼"),
+    ("fa-bug", "bug", "🐛"),
+    ("fa-camera", "camera", "📷"),
+    ("fa-cart-plus", "cart", "🛒"),
+    ("fa-certificate", "certificate", "🏅"),
+    ("fa-coffee", "coffee", "☕"),
+    ("fa-mug-saucer", "coffee", "☕"),
+    ("fa-cloud", "cloud", "☁️"),
+    ("fa-comment", "comment", "💬"),
+    ("fa-cube", "cube", "🧊"),
+    ("fa-cutlery", "cutlery", "🍴"),
+    ("fa-utensils", "cutlery", "🍴"),
+    ("fa-database", "database", "💾"),
+    ("fa-diamond", "diamond", "💎"),
+    ("fa-gem", "diamond", "💎"),
+    ("fa-exclamation-circle", "exclamation", "❗"),
+    ("fa-circle-exclamation", "exclamation", "❗"),
+    ("fa-eye", "eye", "👁️"),
+    ("fa-flag", "flag", "🚩"),
+    ("fa-flask", "flask", "🧪"),
+    ("fa-futbol", "football", "⚽"),
+    ("fa-gamepad", "gamepad", "🎮"),
+    ("fa-graduation-cap", "graduation", "🎓"),
+    ("fa-anchor", "anchor", "⚓"),
+    ("fa-apple-whole", "apple", "🍎"),
+    ("fa-bicycle", "bicycle", "🚲"),
+    ("fa-book", "book", "📖"),
+    ("fa-crown", "crown", "👑"),
+    ("fa-dragon", "dragon", "🐉"),
+    ("fa-feather", "feather", "🪶"),
+    ("fa-fish", "fish", "🐟"),
+    ("fa-guitar", "guitar", "🎸"),
+    ("fa-key", "key", "🔑"),
+    ("fa-leaf", "leaf", "🍃"),
+    ("fa-lemon", "lemon", "🍋"),
+    ("fa-moon", "moon", "🌙"),
+    ("fa-music", "music", "🎵"),
+    ("fa-paw", "paw", "🐾"),
+    ("fa-star", "star", "⭐"),
+    ("fa-sun", "sun", "☀️"),
+    ("fa-tree", "tree", "🌳"),
+    ("fa-umbrella", "umbrella", "☂️"),
+    ("fa-brands fa-rust", "rust", "🦀"),
+];
+
+// Define a struct to hold an icon of the rich fingerprint and its color
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FingerprintIcon {
+    pub icon: &'static str,
+    pub color: Option<&'static str>,
+}
+
+const ICONS: [&str; 46] = [
+    "fa-hashtag",
+    "fa-heart",
+    "fa-hotel",
+    "fa-university",
+    "fa-plug",
+    "fa-ambulance",
+    "fa-bus",
+    "fa-car",
+    "fa-plane",
+    "fa-rocket",
+    "fa-ship",
+    "fa-subway",
+    "fa-truck",
+    "fa-jpy",
+    "fa-eur",
+    "fa-brands fa-btc",
+    "fa-usd",
+    "fa-gbp",
+    "fa-archive",
+    "fa-area-chart",
+    "fa-bed",
+    "fa-beer",
+    "fa-bell",
+    "fa-binoculars",
+    "fa-birthday-cake",
+    "fa-bomb",
+    "fa-briefcase",
+    "fa-bug",
+    "fa-camera",
+    "fa-cart-plus",
+    "fa-certificate",
+    "fa-coffee",
+    "fa-cloud",
+    "fa-coffee",
+    "fa-comment",
+    "fa-cube",
+    "fa-cutlery",
+    "fa-database",
+    "fa-diamond",
+    "fa-exclamation-circle",
+    "fa-eye",
+    "fa-flag",
+    "fa-flask",
+    "fa-futbol",
+    "fa-gamepad",
+    "fa-graduation-cap",
+];
+
+/*
+    Retrieves an icon based on the provided SHA256 hash.
+
+    # Arguments
+
+    * `sha256` - A hexadecimal SHA256 hash string.
+
+    # Returns
+
+    * The name of the icon corresponding to the hash.
+*/
+fn get_icon(sha256: &str) -> &'static str {
+    // Convert the hexadecimal SHA256 hash to a u32 integer
+    let sum = match u32::from_str_radix(sha256, 16) {
+        Ok(parsed_value) => parsed_value,
+        Err(_) => return "default_icon",
+    };
+    // Calculate the index of the icon based on the hash value
+    let index = sum % ICONS.len() as u32;
+    ICONS[index as usize]
+}
+
+/*
+    Calculates and returns a fingerprint based on the input string.
+
+    # Arguments
+
+    * `input` - The input string to calculate the fingerprint from.
+
+    # Returns
+
+    * A vector containing the icons corresponding to the fingerprint segments.
+*/
+pub fn fingerprint_calculate(input: &str) -> Vec<String> {
+    let mut hashed_input_icons: Vec<String> = Vec::new();
+
+    if input.is_empty() {
+        for icon in PLACEHOLDER_ICONS {
+            hashed_input_icons.push(icon.to_string());
+        }
+    } else {
+        // Calculate the SHA256 fingerprint of the input string
+        let hashed_input: String = get_fingerprint(input)
+            // Convert the byte array to a hexadecimal string
+            .iter()
+            .fold(String::new(), |mut acc, &byte| {
+                write!(acc, "{:02x}", byte).expect("Failed to write to string");
+                acc
+            });
+
+        // Divide the hashed input into segments and assign icons
+        let mut x = 0;
+        let mut y = 6;
+        for _i in 0..3 {
+            // Get an icon corresponding to each segment of the hashed input
+            let hashed_segment_icon = get_icon(&hashed_input[x..y]);
+            hashed_input_icons.push(hashed_segment_icon.to_string());
+            x += 6;
+            y += 6;
+        }
+    }
+    // Return the vector of hashed input icons
+    hashed_input_icons
+}
+
+/*
+    Calculates and returns a rich fingerprint based on the input string.
+
+    # Arguments
+
+    * `input` - The input string to calculate the fingerprint from.
+    * `slots` - The number of icons, clamped between `RICH_SLOTS` and `MAX_RICH_SLOTS`.
+
+    # Returns
+
+    * A vector containing the colored icons, or the uncolored placeholder icons for an empty input.
+*/
+pub fn fingerprint_rich(input: &str, slots: usize) -> Vec<FingerprintIcon> {
+    if input.is_empty() {
+        return PLACEHOLDER_ICONS
+            .iter()
+            .map(|icon| FingerprintIcon { icon, color: None })
+            .collect();
+    }
+
+    let hash = get_fingerprint(input);
+    hash.chunks(SLOT_BYTES)
+        .take(slots.clamp(RICH_SLOTS, MAX_RICH_SLOTS))
+        .map(|slot| {
+            let icon = u32::from_be_bytes([0, slot[0], slot[1], slot[2]]);
+            let color = u32::from_be_bytes([0, slot[3], slot[4], slot[5]]);
+            FingerprintIcon {
+                icon: RICH_ICONS[icon as usize % RICH_ICONS.len()],
+                color: Some(COLORS[color as usize % COLORS.len()]),
+            }
+        })
+        .collect()
+}
+
+/*
+    Returns the word describing an icon.
+
+    # Arguments
+
+    * `icon` - The classes of the icon, as returned by `fingerprint_calculate` or `fingerprint_rich`.
+
+    # Returns
+
+    * A word such as "car", or `None` for an unknown icon.
+*/
+pub fn icon_name(icon: &str) -> Option<&'static str> {
+    ICON_LABELS
+        .iter()
+        .find(|(known, _, _)| *known == icon)
+        .map(|(_, name, _)| *name)
+}
+
+/*
+    Returns the emoji depicting an icon.
+
+    # Arguments
+
+    * `icon` - The classes of the icon, as returned by `fingerprint_calculate` or `fingerprint_rich`.
+
+    # Returns
+
+    * An emoji such as "🚗", or `None` for an unknown icon.
+*/
+pub fn icon_emoji(icon: &str) -> Option<&'static str> {
+    ICON_LABELS
+        .iter()
+        .find(|(known, _, _)| *known == icon)
+        .map(|(_, _, emoji)| *emoji)
+}
+
+/*
+    Describes a fingerprint with words.
+
+    # Arguments
+
+    * `icons` - The classes of the icons of the fingerprint.
+
+    # Returns
+
+    * The words of the icons separated by commas, such as "car, hashtag, bug".
+*/
+pub fn fingerprint_text<S: AsRef<str>>(icons: &[S]) -> String {
+    icons
+        .iter()
+        .map(|icon| icon_name(icon.as_ref()).unwrap_or("unknown"))
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_calculate_empty_input() {
+        let input = "";
+        let expected_output = vec![
+            "fa-heart".to_string(),
+            "fa-brands fa-rust".to_string(),
+            "fa-rocket".to_string(),
+        ];
+
+        // Act
+        let result = fingerprint_calculate(input);
+
+        // Assert
+        assert_eq!(result, expected_output);
+    }
+
+    #[test]
+    fn test_fingerprint_calculate_non_empty_input_0() {
+        let input = "lorem ipsum";
+        let expected_output = vec![
+            "fa-car".to_string(),     // Provide expected icon for the first segment
+            "fa-hashtag".to_string(), // Provide expected icon for the second segment
+            "fa-bug".to_string(),     // Provide expected icon for the third segment
+        ];
+
+        let result = fingerprint_calculate(input);
+
+        assert_eq!(result, expected_output);
+    }
+
+    #[test]
+    fn test_fingerprint_calculate_non_empty_input_1() {
+        let input = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis.";
+        let expected_output = vec![
+            "fa-rocket".to_string(),  // Provide expected icon for the first segment
+            "fa-coffee".to_string(),  // Provide expected icon for the second segment
+            "fa-cutlery".to_string(), // Provide expected icon for the third segment
+        ];
+
+        let result = fingerprint_calculate(input);
+
+        assert_eq!(result, expected_output);
+    }
+
+    #[test]
+    fn test_fingerprint_calculate_non_empty_input_2() {
+        let input = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis. Eu consequat ac felis donec et odio pellentesque diam volutpat. Mi in nulla posuere sollicitudin. Euismod quis viverra nibh cras. Tristique nulla aliquet enim tortor at auctor urna nunc. Dignissim convallis aenean et tortor at. Turpis egestas pretium aenean pharetra. Sed vulputate odio ut enim. Faucibus et molestie ac feugiat. Donec ultrices tincidunt arcu non sodales neque sodales ut etiam. Donec pretium vulputate sapien nec sagittis aliquam malesuada. Mauris cursus mattis molestie a iaculis. Hendrerit gravida rutrum quisque non. Enim nulla aliquet porttitor lacus luctus accumsan tortor posuere. Et leo duis ut diam quam nulla. Quam lacus suspendisse faucibus interdum posuere lorem. Adipiscing elit ut aliquam purus sit amet. Consectetur adipiscing elit ut aliquam purus sit amet. Erat imperdiet sed euismod nisi porta lorem mollis.";
+        let expected_output = vec![
+            "fa-university".to_string(), // Provide expected icon for the first segment
+            "fa-coffee".to_string(),     // Provide expected icon for the second segment
+            "fa-hotel".to_string(),      // Provide expected icon for the third segment
+        ];
+
+        let result = fingerprint_calculate(input);
+
+        assert_eq!(result, expected_output);
+    }
+
+    #[test]
+    fn test_legacy_icons_are_unchanged() {
+        // The legacy table keeps its duplicate so existing fingerprints stay the same
+        assert_eq!(ICONS.len(), 46);
+        assert_eq!(ICONS[31], "fa-coffee");
+        assert_eq!(ICONS[33], "fa-coffee");
+        assert_eq!(get_icon("000000"), "fa-hashtag");
+        assert_eq!(get_icon("ffffff"), "fa-university");
+    }
+
+    #[test]
+    fn test_fingerprint_rich() {
+        let fingerprint = fingerprint_rich("lorem ipsum", 3);
+
+        assert_eq!(fingerprint.len(), 3);
+        assert_eq!(fingerprint_rich("lorem ipsum", 4)[..3], fingerprint[..]);
+        assert_eq!(
+            fingerprint,
+            vec![
+                FingerprintIcon {
+                    icon: "fa-feather",
+                    color: Some("#006CDB")
+                },
+                FingerprintIcon {
+                    icon: "fa-gamepad",
+                    color: Some("#006CDB")
+                },
+                FingerprintIcon {
+                    icon: "fa-heart",
+                    color: Some("#006CDB")
+                },
+            ]
+        );
+        assert_eq!(fingerprint_rich("", 4).len(), 3);
+        assert!(fingerprint_rich("", 4)
+            .iter()
+            .all(|icon| icon.color.is_none()));
+    }
+
+    #[test]
+    fn test_rich_icons_are_distinct() {
+        let mut icons = RICH_ICONS.to_vec();
+        icons.sort();
+        icons.dedup();
+
+        assert_eq!(icons.len(), RICH_ICONS.len());
+    }
+
+    #[test]
+    fn test_fingerprint_rich_distribution() {
+        // Every icon and color should be picked about as often as the others
+        let samples = 64 * 14 * 20;
+        let mut icons = vec![0; RICH_ICONS.len()];
+        let mut colors = vec![0; COLORS.len()];
+        for i in 0..samples {
+            for icon in fingerprint_rich(&i.to_string(), MAX_RICH_SLOTS) {
+                icons[RICH_ICONS
+                    .iter()
+                    .position(|known| *known == icon.icon)
+                    .unwrap()] += 1;
+                colors[COLORS
+                    .iter()
+                    .position(|known| Some(*known) == icon.color)
+                    .unwrap()] += 1;
+            }
+        }
+
+        // Bounds above the 99.9th percentile of the chi-square distribution, for 63 and 13 degrees of freedom
+        let tables = [
+            (icons, samples * 4 / 64, 104.0),
+            (colors, samples * 4 / 14, 35.0),
+        ];
+        for (counts, expected, bound) in tables {
+            let chi_square: f64 = counts
+                .iter()
+                .map(|&count| (count as f64 - expected as f64).powi(2) / expected as f64)
+                .sum();
+            assert!(
+                chi_square < bound,
+                "chi-square {} for {:?}",
+                chi_square,
+                counts
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_icon_has_a_name_and_an_emoji() {
+        for icon in ICONS.iter().chain(&RICH_ICONS).chain(&PLACEHOLDER_ICONS) {
+            assert!(icon_name(icon).is_some(), "{} has no name", icon);
+            assert!(icon_emoji(icon).is_some(), "{} has no emoji", icon);
+        }
+    }
+
+    #[test]
+    fn test_rich_icon_names_are_distinct() {
+        let mut names: Vec<&str> = RICH_ICONS
+            .iter()
+            .filter_map(|icon| icon_name(icon))
+            .collect();
+        names.sort();
+        names.dedup();
+
+        assert_eq!(names.len(), RICH_ICONS.len());
+    }
+
+    #[test]
+    fn test_fingerprint_text() {
+        assert_eq!(
+            fingerprint_text(&fingerprint_calculate("lorem ipsum")),
+            "car, hashtag, bug"
+        );
+        assert_eq!(icon_emoji("fa-car"), Some("🚗"));
+    }
+}
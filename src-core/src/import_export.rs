@@ -0,0 +1,770 @@
+/*
+    This module contains functions for importing and exporting saved profiles using
+    the JSON format of the LessPass browser extension.
+
+    The `export_profiles` function serializes profiles into a LessPass compatible JSON array,
+    or into an object holding that array and the site aliases when there are any.
+    The `export_profiles_encrypted` function does the same and encrypts the result with a key
+    derived from the master password.
+    The `import_profiles` function parses a plain or encrypted export, in either form, back
+    into profiles and aliases.
+
+    The file also includes unit tests checking that generated passwords survive a round trip.
+*/
+
+use std::fmt;
+use std::fmt::Write;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use serde::{Deserialize, Serialize};
+
+use crate::aliases::{Alias, Aliases};
+use crate::mnemonic::MNEMONIC_WORDS;
+use crate::passgen::{Algorithm, DEFAULT_ITERATIONS};
+use crate::passphrase::MAX_WORDS;
+use crate::pin::{MAX_PIN_LENGTH, MIN_PIN_LENGTH};
+use crate::profiles::{Profile, Profiles};
+use crate::settings::{
+    update_disabled_characters, Settings, MODE_MNEMONIC, MODE_PASSPHRASE, MODE_PIN,
+};
+use crate::totp::{DEFAULT_DIGITS, MAX_DIGITS, MIN_DIGITS};
+
+const CIPHER: &str = "aes-256-gcm";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+// Longest password of the LessPass form
+const MAX_LENGTH: u64 = 35;
+
+// Define a struct matching a profile of the LessPass browser extension
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LessPassProfile {
+    pub site: String,
+    pub login: String,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    #[serde(alias = "numbers")]
+    pub digits: bool,
+    pub symbols: bool,
+    pub length: u64,
+    pub counter: u64,
+    #[serde(default = "default_version")]
+    pub version: u64,
+    // Key derivation parameters, only written when they differ from the LessPass defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<Algorithm>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<u64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub excluded_characters: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub allowed_symbols: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub no_lookalikes: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub policy: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub sub_counter: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<PassphraseProfile>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pin: bool,
+    // Number of words of the mnemonic, only written for mnemonic profiles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<u64>,
+    #[serde(
+        default = "default_totp_digits",
+        skip_serializing_if = "is_default_totp_digits"
+    )]
+    pub totp_digits: u64,
+}
+
+// Define a struct holding the passphrase options of a profile
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PassphraseProfile {
+    pub words: u64,
+    pub separator: String,
+    pub capitalize: bool,
+}
+
+// Define an enum of the forms of an export, the LessPass array or an object with aliases
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+enum ExportFile {
+    Profiles(Vec<LessPassProfile>),
+    WithAliases {
+        profiles: Vec<LessPassProfile>,
+        #[serde(default)]
+        aliases: Vec<Alias>,
+    },
+}
+
+// Define a struct holding an export encrypted with the master password
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+struct EncryptedExport {
+    cipher: String,
+    salt: String,
+    nonce: String,
+    data: String,
+}
+
+// Define a struct holding the result of an import
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Import {
+    pub profiles: Vec<Profile>,
+    pub aliases: Vec<Alias>,
+    pub skipped: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportError {
+    InvalidFormat,
+    Decryption,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::InvalidFormat => write!(f, "This file is not a LessPass profiles export"),
+            ImportError::Decryption => {
+                write!(
+                    f,
+                    "Unable to decrypt this file with the current master password"
+                )
+            }
+        }
+    }
+}
+
+fn default_version() -> u64 {
+    2
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+fn default_totp_digits() -> u64 {
+    DEFAULT_DIGITS as u64
+}
+
+fn is_default_totp_digits(value: &u64) -> bool {
+    *value == default_totp_digits()
+}
+
+impl From<&Profile> for LessPassProfile {
+    fn from(profile: &Profile) -> Self {
+        Self {
+            site: profile.site.clone(),
+            login: profile.login.clone(),
+            lowercase: profile.settings.lowercase != 0,
+            uppercase: profile.settings.uppercase != 0,
+            digits: profile.settings.numbers != 0,
+            symbols: profile.settings.symbols != 0,
+            length: profile.settings.size,
+            counter: profile.settings.counter,
+            version: profile.settings.version,
+            algorithm: Some(Algorithm::from_digest_size(profile.settings.algorithm))
+                .filter(|algorithm| *algorithm != Algorithm::default()),
+            iterations: Some(profile.settings.iterations)
+                .filter(|iterations| *iterations != DEFAULT_ITERATIONS as u64),
+            excluded_characters: profile.settings.excluded_characters.clone(),
+            allowed_symbols: profile.settings.allowed_symbols.clone(),
+            no_lookalikes: profile.settings.no_lookalikes != 0,
+            policy: profile.settings.policy.clone(),
+            sub_counter: profile.settings.sub_counter,
+            passphrase: (profile.settings.mode == MODE_PASSPHRASE).then(|| PassphraseProfile {
+                words: profile.settings.words,
+                separator: profile.settings.separator.clone(),
+                capitalize: profile.settings.capitalize != 0,
+            }),
+            pin: profile.settings.mode == MODE_PIN,
+            mnemonic: (profile.settings.mode == MODE_MNEMONIC)
+                .then_some(profile.settings.mnemonic_words),
+            totp_digits: profile.settings.totp_digits,
+        }
+    }
+}
+
+impl LessPassProfile {
+    /*
+        Converts the LessPass profile into a saved profile.
+
+        Returns:
+        The matching `Profile`, or `None` if the profile uses an unsupported LessPass version,
+        an output other than a password with LessPass v1, or a number out of range.
+    */
+    pub fn to_profile(&self) -> Option<Profile> {
+        if !matches!(self.version, 1 | 2) {
+            return None;
+        }
+        if self.version == 1 && (self.passphrase.is_some() || self.pin || self.mnemonic.is_some()) {
+            return None;
+        }
+        if !self.is_in_range() {
+            return None;
+        }
+        let iterations = self.iterations.unwrap_or(DEFAULT_ITERATIONS as u64);
+
+        let mut settings = Settings {
+            size: self.length,
+            counter: self.counter,
+            lowercase: self.lowercase as u64,
+            uppercase: self.uppercase as u64,
+            numbers: self.digits as u64,
+            symbols: self.symbols as u64,
+            version: self.version,
+            algorithm: self.algorithm.unwrap_or_default().digest_size(),
+            iterations,
+            excluded_characters: self.excluded_characters.clone(),
+            allowed_symbols: self.allowed_symbols.clone(),
+            no_lookalikes: self.no_lookalikes as u64,
+            policy: self.policy.clone(),
+            sub_counter: self.sub_counter,
+            totp_digits: self.totp_digits,
+            ..Settings::default()
+        };
+        if let Some(passphrase) = &self.passphrase {
+            settings.mode = MODE_PASSPHRASE;
+            settings.words = passphrase.words;
+            settings.separator = passphrase.separator.clone();
+            settings.capitalize = passphrase.capitalize as u64;
+        }
+        if self.pin {
+            settings.mode = MODE_PIN;
+        }
+        if let Some(words) = self.mnemonic {
+            settings.mode = MODE_MNEMONIC;
+            settings.mnemonic_words = words;
+        }
+        settings.disabled = update_disabled_characters(&settings);
+
+        Some(Profile {
+            site: self.site.clone(),
+            login: self.login.clone(),
+            settings,
+        })
+    }
+
+    // Returns whether the numbers of the profile are in the ranges the frontends offer, as
+    // they would otherwise be truncated or clamped into another password
+    fn is_in_range(&self) -> bool {
+        let lengths = if self.pin {
+            MIN_PIN_LENGTH as u64..=MAX_PIN_LENGTH as u64
+        } else {
+            1..=MAX_LENGTH
+        };
+        let positive_u32 = 1..=u32::MAX as u64;
+        lengths.contains(&self.length)
+            && positive_u32.contains(&self.counter)
+            && positive_u32.contains(&self.iterations.unwrap_or(DEFAULT_ITERATIONS as u64))
+            && self
+                .passphrase
+                .as_ref()
+                .is_none_or(|passphrase| (1..=MAX_WORDS as u64).contains(&passphrase.words))
+            && self
+                .mnemonic
+                .is_none_or(|words| MNEMONIC_WORDS.contains(&(words as usize)))
+            && (MIN_DIGITS as u64..=MAX_DIGITS as u64).contains(&self.totp_digits)
+    }
+}
+
+/*
+    Serializes the saved profiles and aliases into JSON.
+
+    Arguments:
+    - `profiles`: A reference to the saved `Profiles`.
+    - `aliases`: A reference to the site `Aliases`.
+
+    Returns:
+    A `String` containing the JSON export, a LessPass compatible array if there is no alias.
+*/
+pub fn export_profiles(profiles: &Profiles, aliases: &Aliases) -> String {
+    let records: Vec<LessPassProfile> =
+        profiles.entries.iter().map(LessPassProfile::from).collect();
+    let export = if aliases.entries.is_empty() {
+        ExportFile::Profiles(records)
+    } else {
+        ExportFile::WithAliases {
+            profiles: records,
+            aliases: aliases.entries.clone(),
+        }
+    };
+    serde_json::to_string_pretty(&export).expect("profiles serialize to JSON")
+}
+
+/*
+    Serializes the saved profiles and aliases and encrypts them with AES-256-GCM.
+
+    The key is derived from the master password and a random salt with the
+    same PBKDF2 parameters LessPass uses to generate passwords.
+
+    Arguments:
+    - `profiles`: A reference to the saved `Profiles`.
+    - `aliases`: A reference to the site `Aliases`.
+    - `master_password`: A string slice representing the master password.
+
+    Returns:
+    A `String` containing the encrypted JSON export.
+*/
+pub fn export_profiles_encrypted(
+    profiles: &Profiles,
+    aliases: &Aliases,
+    master_password: &str,
+) -> String {
+    let mut salt = [0u8; SALT_LENGTH];
+    let mut nonce = [0u8; NONCE_LENGTH];
+    getrandom::getrandom(&mut salt).expect("random salt");
+    getrandom::getrandom(&mut nonce).expect("random nonce");
+
+    let cipher = export_cipher(master_password, &salt);
+    let data = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            export_profiles(profiles, aliases).as_bytes(),
+        )
+        .expect("profiles encrypt");
+
+    let export = EncryptedExport {
+        cipher: CIPHER.to_string(),
+        salt: to_hex(&salt),
+        nonce: to_hex(&nonce),
+        data: to_hex(&data),
+    };
+    serde_json::to_string_pretty(&export).expect("export serializes to JSON")
+}
+
+/*
+    Parses a plain or encrypted export back into profiles and aliases.
+
+    Arguments:
+    - `contents`: A string slice containing the exported file.
+    - `master_password`: A string slice representing the master password, used for encrypted exports.
+
+    Returns:
+    An `Import` with the supported profiles, the aliases and the number of skipped profiles,
+    or an `ImportError`.
+*/
+pub fn import_profiles(contents: &str, master_password: &str) -> Result<Import, ImportError> {
+    let export: ExportFile = match serde_json::from_str::<EncryptedExport>(contents) {
+        Ok(export) => {
+            let decrypted = decrypt_export(&export, master_password)?;
+            serde_json::from_slice(&decrypted).map_err(|_| ImportError::InvalidFormat)?
+        }
+        Err(_) => serde_json::from_str(contents).map_err(|_| ImportError::InvalidFormat)?,
+    };
+
+    let (records, aliases) = match export {
+        ExportFile::Profiles(records) => (records, Vec::new()),
+        ExportFile::WithAliases { profiles, aliases } => (profiles, aliases),
+    };
+
+    let mut import = Import {
+        aliases,
+        ..Import::default()
+    };
+    for record in records {
+        match record.to_profile() {
+            Some(profile) => import.profiles.push(profile),
+            None => import.skipped += 1,
+        }
+    }
+    Ok(import)
+}
+
+fn decrypt_export(export: &EncryptedExport, master_password: &str) -> Result<Vec<u8>, ImportError> {
+    if export.cipher != CIPHER {
+        return Err(ImportError::InvalidFormat);
+    }
+    let salt = from_hex(&export.salt).ok_or(ImportError::InvalidFormat)?;
+    let nonce = from_hex(&export.nonce).ok_or(ImportError::InvalidFormat)?;
+    let data = from_hex(&export.data).ok_or(ImportError::InvalidFormat)?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(ImportError::InvalidFormat);
+    }
+
+    export_cipher(master_password, &salt)
+        .decrypt(Nonce::from_slice(&nonce), data.as_slice())
+        .map_err(|_| ImportError::Decryption)
+}
+
+// Derives the export encryption key from the master password
+fn export_cipher(master_password: &str, salt: &[u8]) -> Aes256Gcm {
+    let key =
+        lesspass::generate_entropy(master_password, salt, lesspass::Algorithm::SHA256, 100000);
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key[..32]))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut acc, &byte| {
+        write!(acc, "{:02x}", byte).expect("Failed to write to string");
+        acc
+    })
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passgen::generate_password;
+    use crate::settings::password_options;
+
+    const LESSPASS_EXPORT: &str = r#"[
+        {
+            "id": "0b4a3ff8-e5b5-4a27-8f7a-4ac0a7dba3f4",
+            "site": "example.org",
+            "login": "contact@example.org",
+            "lowercase": true,
+            "uppercase": true,
+            "numbers": true,
+            "symbols": true,
+            "length": 16,
+            "counter": 1,
+            "version": 2
+        },
+        {
+            "site": "lorem ipsum",
+            "login": "lorem ipsum",
+            "lowercase": true,
+            "uppercase": false,
+            "digits": false,
+            "symbols": false,
+            "length": 35,
+            "counter": 100,
+            "version": 2
+        },
+        {
+            "site": "example.org",
+            "login": "contact@example.org",
+            "lowercase": true,
+            "uppercase": true,
+            "numbers": true,
+            "symbols": true,
+            "length": 12,
+            "counter": 1,
+            "version": 1
+        },
+        {
+            "site": "future.example.org",
+            "login": "contact@example.org",
+            "lowercase": true,
+            "uppercase": true,
+            "numbers": true,
+            "symbols": true,
+            "length": 16,
+            "counter": 1,
+            "version": 3
+        }
+    ]"#;
+
+    fn generate_all(profiles: &[Profile], master_password: &str) -> Vec<String> {
+        profiles
+            .iter()
+            .map(|profile| {
+                generate_password(password_options(
+                    &profile.site,
+                    &profile.login,
+                    master_password,
+                    &profile.settings,
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_import_lesspass_profiles() {
+        let import = import_profiles(LESSPASS_EXPORT, "").expect("valid export");
+
+        assert_eq!(import.skipped, 1);
+        assert_eq!(import.profiles.len(), 3);
+        assert_eq!(import.profiles[1].settings.size, 35);
+        assert_eq!(import.profiles[1].settings.counter, 100);
+        assert_eq!(import.profiles[1].settings.disabled, "a-z");
+        assert_eq!(import.profiles[2].settings.version, 1);
+    }
+
+    #[test]
+    fn test_import_generates_lesspass_passwords() {
+        let import = import_profiles(LESSPASS_EXPORT, "").expect("valid export");
+
+        let passwords = generate_all(&import.profiles, "password");
+
+        assert_eq!(passwords[0], "WHLpUL)e00[iHR+w");
+        assert_eq!(passwords[2], "izIS5@ozYM2?");
+    }
+
+    #[test]
+    fn test_import_skips_legacy_outputs() {
+        let export = r#"[{"site": "door.example.org", "login": "", "lowercase": true,
+            "uppercase": true, "digits": true, "symbols": true, "length": 6, "counter": 1,
+            "version": 1, "pin": true}]"#;
+
+        let import = import_profiles(export, "").expect("valid export");
+
+        assert_eq!(import.skipped, 1);
+        assert!(import.profiles.is_empty());
+    }
+
+    #[test]
+    fn test_import_skips_invalid_iterations() {
+        let profile = |iterations: u64| {
+            format!(
+                r#"{{"site": "example.org", "login": "", "lowercase": true, "uppercase": true,
+                "digits": true, "symbols": true, "length": 16, "counter": 1, "version": 2,
+                "iterations": {}}}"#,
+                iterations
+            )
+        };
+        let export = format!(
+            "[{}, {}, {}]",
+            profile(0),
+            profile(u32::MAX as u64 + 1),
+            profile(u32::MAX as u64)
+        );
+
+        let import = import_profiles(&export, "").expect("valid export");
+
+        assert_eq!(import.skipped, 2);
+        assert_eq!(import.profiles.len(), 1);
+        assert_eq!(import.profiles[0].settings.iterations, u32::MAX as u64);
+    }
+
+    // Builds an export of one profile with the given length, counter and extra fields
+    fn export_with(length: u64, counter: u64, extra: &str) -> String {
+        format!(
+            r#"[{{"site": "example.org", "login": "", "lowercase": true, "uppercase": true,
+            "digits": true, "symbols": true, "length": {}, "counter": {}, "version": 2{}}}]"#,
+            length, counter, extra
+        )
+    }
+
+    #[test]
+    fn test_import_skips_invalid_lengths() {
+        let pin = r#", "pin": true"#;
+        for (length, extra) in [(0, ""), (36, ""), (3, pin), (13, pin)] {
+            let export = export_with(length, 1, extra);
+
+            let import = import_profiles(&export, "").expect("valid export");
+
+            assert_eq!(import.skipped, 1, "{} should be skipped", export);
+        }
+    }
+
+    #[test]
+    fn test_import_skips_invalid_counters() {
+        for counter in [0, u32::MAX as u64 + 1] {
+            let import = import_profiles(&export_with(16, counter, ""), "").expect("valid export");
+
+            assert_eq!(import.skipped, 1, "counter {} should be skipped", counter);
+        }
+    }
+
+    #[test]
+    fn test_import_skips_invalid_passphrase_words() {
+        for words in [0, MAX_WORDS + 1] {
+            let extra = format!(
+                r#", "passphrase": {{"words": {}, "separator": "-", "capitalize": false}}"#,
+                words
+            );
+
+            let import = import_profiles(&export_with(16, 1, &extra), "").expect("valid export");
+
+            assert_eq!(import.skipped, 1, "{} words should be skipped", words);
+        }
+    }
+
+    #[test]
+    fn test_import_skips_invalid_mnemonic_words() {
+        let import =
+            import_profiles(&export_with(16, 1, r#", "mnemonic": 18"#), "").expect("valid export");
+        assert_eq!(import.skipped, 1);
+
+        let import =
+            import_profiles(&export_with(16, 1, r#", "mnemonic": 24"#), "").expect("valid export");
+        assert_eq!(import.profiles[0].settings.mnemonic_words, 24);
+    }
+
+    #[test]
+    fn test_import_skips_invalid_totp_digits() {
+        for digits in [0, 5, 9, 20] {
+            let extra = format!(r#", "totp_digits": {}"#, digits);
+
+            let import = import_profiles(&export_with(16, 1, &extra), "").expect("valid export");
+
+            assert_eq!(import.skipped, 1, "{} digits should be skipped", digits);
+        }
+        let import = import_profiles(&export_with(16, 1, r#", "totp_digits": 8"#), "")
+            .expect("valid export");
+        assert_eq!(import.profiles[0].settings.totp_digits, 8);
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let import = import_profiles(LESSPASS_EXPORT, "").expect("valid export");
+        let profiles = Profiles {
+            entries: import.profiles,
+        };
+
+        let reimport = import_profiles(&export_profiles(&profiles, &Aliases::default()), "")
+            .expect("valid export");
+
+        assert_eq!(reimport.skipped, 0);
+        assert_eq!(reimport.profiles, profiles.entries);
+        assert_eq!(
+            generate_all(&reimport.profiles, "lorem ipsum"),
+            generate_all(&profiles.entries, "lorem ipsum")
+        );
+    }
+
+    #[test]
+    fn test_export_kdf_settings() {
+        let mut profiles = Profiles::default();
+        profiles.save("example.org", "contact@example.org", &Settings::default());
+        profiles.save(
+            "hardened.example.org",
+            "contact@example.org",
+            &Settings {
+                algorithm: 512,
+                iterations: 500000,
+                excluded_characters: "\"'".to_string(),
+                policy: "bank".to_string(),
+                sub_counter: 2,
+                ..Settings::default()
+            },
+        );
+        profiles.save(
+            "tv.example.org",
+            "contact@example.org",
+            &Settings {
+                mode: MODE_PASSPHRASE,
+                words: 4,
+                separator: " ".to_string(),
+                ..Settings::default()
+            },
+        );
+
+        let export = export_profiles(&profiles, &Aliases::default());
+        let reimport = import_profiles(&export, "").expect("valid export");
+
+        assert_eq!(export.matches("\"algorithm\": \"sha512\"").count(), 1);
+        assert_eq!(export.matches("\"iterations\": 500000").count(), 1);
+        assert_eq!(export.matches("\"excluded_characters\"").count(), 1);
+        assert_eq!(export.matches("\"sub_counter\": 2").count(), 1);
+        assert_eq!(export.matches("\"passphrase\"").count(), 1);
+        assert_eq!(reimport.profiles, profiles.entries);
+    }
+
+    #[test]
+    fn test_export_pin_profile() {
+        let mut profiles = Profiles::default();
+        profiles.save(
+            "door.example.org",
+            "contact@example.org",
+            &Settings {
+                mode: MODE_PIN,
+                size: 6,
+                totp_digits: 8,
+                ..Settings::default()
+            },
+        );
+
+        let export = export_profiles(&profiles, &Aliases::default());
+        let reimport = import_profiles(&export, "").expect("valid export");
+
+        assert_eq!(export.matches("\"pin\": true").count(), 1);
+        assert_eq!(export.matches("\"totp_digits\": 8").count(), 1);
+        assert_eq!(reimport.profiles, profiles.entries);
+    }
+
+    #[test]
+    fn test_export_mnemonic_profile() {
+        let mut profiles = Profiles::default();
+        profiles.save(
+            "wallet.example.org",
+            "contact@example.org",
+            &Settings {
+                mode: MODE_MNEMONIC,
+                mnemonic_words: 24,
+                ..Settings::default()
+            },
+        );
+
+        let export = export_profiles(&profiles, &Aliases::default());
+        let reimport = import_profiles(&export, "").expect("valid export");
+
+        assert_eq!(export.matches("\"mnemonic\": 24").count(), 1);
+        assert_eq!(reimport.profiles, profiles.entries);
+    }
+
+    #[test]
+    fn test_encrypted_export_round_trip() {
+        let import = import_profiles(LESSPASS_EXPORT, "").expect("valid export");
+        let profiles = Profiles {
+            entries: import.profiles,
+        };
+
+        let export = export_profiles_encrypted(&profiles, &Aliases::default(), "master");
+
+        assert!(!export.contains("example.org"));
+        assert_eq!(
+            import_profiles(&export, "wrong master"),
+            Err(ImportError::Decryption)
+        );
+        let reimport = import_profiles(&export, "master").expect("valid export");
+        assert_eq!(reimport.profiles, profiles.entries);
+    }
+
+    #[test]
+    fn test_export_aliases() {
+        let mut profiles = Profiles::default();
+        profiles.save("corp.example.com", "contact", &Settings::default());
+        let mut aliases = Aliases::default();
+        aliases.add("login.example.net", "corp.example.com");
+
+        let export = export_profiles(&profiles, &aliases);
+        let reimport = import_profiles(&export, "").expect("valid export");
+
+        assert!(export.starts_with('{'));
+        assert_eq!(reimport.profiles, profiles.entries);
+        assert_eq!(reimport.aliases, aliases.entries);
+
+        // Without aliases the export stays a LessPass compatible array
+        let export = export_profiles(&profiles, &Aliases::default());
+        assert!(export.starts_with('['));
+        assert!(import_profiles(&export, "").unwrap().aliases.is_empty());
+    }
+
+    #[test]
+    fn test_encrypted_export_aliases() {
+        let mut aliases = Aliases::default();
+        aliases.add("login.example.net", "corp.example.com");
+
+        let export = export_profiles_encrypted(&Profiles::default(), &aliases, "master");
+
+        assert!(!export.contains("example"));
+        let reimport = import_profiles(&export, "master").expect("valid export");
+        assert_eq!(reimport.aliases, aliases.entries);
+    }
+
+    #[test]
+    fn test_import_invalid_file() {
+        assert_eq!(
+            import_profiles("not json", ""),
+            Err(ImportError::InvalidFormat)
+        );
+    }
+}
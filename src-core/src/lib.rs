@@ -0,0 +1,29 @@
+/*
+    This crate contains the password generation code and the settings data model shared by
+    the RustedLessPass frontends.
+
+    It has no browser dependency, so it can be used by the web application, the Tauri
+    application and the command-line interface alike. Persisting the settings is left to
+    each frontend.
+*/
+
+pub mod aliases;
+pub mod domain;
+pub mod fingerprintgen;
+pub mod import_export;
+pub mod mnemonic;
+pub mod passgen;
+pub mod passgen_v1;
+pub mod passphrase;
+pub mod pin;
+pub mod policy;
+pub mod preferences;
+pub mod profiles;
+pub mod readable;
+pub mod render;
+pub mod settings;
+pub mod sshkey;
+pub mod strength;
+pub mod totp;
+pub mod typo_guard;
+mod wordlists;
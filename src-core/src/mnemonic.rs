@@ -0,0 +1,116 @@
+/*
+    This module contains the rendering of passwords as BIP-39 mnemonics, used as recovery
+    codes and crypto wallet seeds.
+
+    The `render_mnemonic` function takes the first bytes of the LessPass entropy and encodes
+    them with `entropy_to_mnemonic`, which appends the BIP-39 checksum (the first bits of the
+    SHA-256 hash of the entropy) and maps every 11 bits onto the BIP-39 English wordlist.
+
+    The file also includes unit tests using the BIP-39 reference vectors.
+*/
+
+use sha2::{Digest, Sha256};
+
+const WORDLIST: &str = include_str!("wordlists/bip39_english.txt");
+
+pub const DEFAULT_MNEMONIC_WORDS: usize = 12;
+pub const MNEMONIC_WORDS: [usize; 2] = [12, 24];
+
+/*
+    Renders a mnemonic from the entropy.
+
+    Arguments:
+    - `entropy`: A slice of at least 32 bytes holding the entropy.
+    - `words`: The number of words, 24 for 256 bits of entropy or 12 for 128 bits.
+
+    Returns:
+    A string containing the words of the mnemonic, separated by spaces.
+*/
+pub fn render_mnemonic(entropy: &[u8], words: usize) -> String {
+    let bytes = if words >= 24 { 32 } else { 16 };
+    entropy_to_mnemonic(&entropy[..bytes])
+}
+
+/*
+    Encodes entropy as a BIP-39 mnemonic with its checksum.
+
+    Arguments:
+    - `entropy`: A slice of 16 to 32 bytes, in multiples of 4.
+
+    Returns:
+    A string containing the words of the mnemonic, separated by spaces.
+*/
+pub fn entropy_to_mnemonic(entropy: &[u8]) -> String {
+    assert!(
+        (16..=32).contains(&entropy.len()) && entropy.len().is_multiple_of(4),
+        "BIP-39 entropy is 16 to 32 bytes long, in multiples of 4"
+    );
+    let wordlist: Vec<&str> = WORDLIST.lines().collect();
+    let checksum = Sha256::digest(entropy);
+    let bits = entropy.len() * 8 + entropy.len() / 4;
+    let bit = |index: usize| {
+        let byte = match entropy.get(index / 8) {
+            Some(byte) => *byte,
+            None => checksum[index / 8 - entropy.len()],
+        };
+        (byte >> (7 - index % 8)) & 1
+    };
+
+    (0..bits / 11)
+        .map(|word| {
+            let index = (0..11).fold(0, |index, i| (index << 1) | bit(word * 11 + i) as usize);
+            wordlist[index]
+        })
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_to_mnemonic_vectors() {
+        // Reference vectors of the BIP-39 specification
+        assert_eq!(
+            entropy_to_mnemonic(&[0x00; 16]),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[0x7f; 16]),
+            "legal winner thank year wave sausage worth useful legal winner thank yellow"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[0x80; 16]),
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[0xff; 16]),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[0x7f; 32]),
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[0xff; 32]),
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote"
+        );
+        assert_eq!(
+            entropy_to_mnemonic(&[
+                0x9e, 0x88, 0x5d, 0x95, 0x2a, 0xd3, 0x62, 0xca, 0xeb, 0x4e, 0xfe, 0x34, 0xa8, 0xe9,
+                0x1b, 0xd2
+            ]),
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"
+        );
+    }
+
+    #[test]
+    fn test_render_mnemonic_lengths() {
+        let entropy = [0x00; 32];
+
+        assert_eq!(render_mnemonic(&entropy, 12).split(' ').count(), 12);
+        assert_eq!(render_mnemonic(&entropy, 24).split(' ').count(), 24);
+        assert!(render_mnemonic(&entropy, 24).ends_with(" art"));
+    }
+}
@@ -0,0 +1,509 @@
+/*
+    This module contains functions for generating passwords based on various options
+    using the LessPass algorithm.
+
+    The `generate_password` function generates a password based on provided parameters,
+    using the LessPass v1 algorithm of `passgen_v1` when the options ask for it.
+    The `Algorithm` enum selects the PBKDF2 digest used to derive the entropy.
+    Options with excluded characters or allowed symbols are rendered by the `render` module.
+    A non-zero sub-counter, set by the `policy` module, re-keys the entropy before rendering.
+    The `Mode` enum selects whether the entropy is rendered as a password, a passphrase, a PIN
+    or a BIP-39 mnemonic.
+    The `generate_charset` function generates the character set based on specified options.
+
+    The file also includes unit tests to ensure the correctness of password generation
+    under different scenarios.
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use lesspass::{self, CharacterSet};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::mnemonic::render_mnemonic;
+use crate::passgen_v1::generate_password_v1;
+use crate::passphrase::render_passphrase;
+use crate::pin::render_pin;
+use crate::render;
+
+pub const DEFAULT_ITERATIONS: u32 = 100000;
+
+// Length of the derived entropy, matching the LessPass key length for every digest
+const ENTROPY_LENGTH: usize = 32;
+
+// Domain separation tag of the entropy derived for a policy sub-counter
+const SUB_COUNTER_TAG: &[u8] = b"rustedlesspass-policy-sub-counter";
+
+// Define an enum of the PBKDF2 digests supported by LessPass
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Algorithm {
+    #[default]
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Algorithm {
+    /*
+        Returns the algorithm matching a digest size in bits, as stored in the settings.
+
+        Arguments:
+        - `bits`: The digest size in bits (256, 384 or 512).
+
+        Returns:
+        The matching `Algorithm`, falling back to SHA-256 for unknown sizes.
+    */
+    pub fn from_digest_size(bits: u64) -> Self {
+        match bits {
+            384 => Algorithm::Sha384,
+            512 => Algorithm::Sha512,
+            _ => Algorithm::Sha256,
+        }
+    }
+
+    pub fn digest_size(self) -> u64 {
+        match self {
+            Algorithm::Sha256 => 256,
+            Algorithm::Sha384 => 384,
+            Algorithm::Sha512 => 512,
+        }
+    }
+
+    pub(crate) fn to_lesspass(self) -> lesspass::Algorithm {
+        match self {
+            Algorithm::Sha256 => lesspass::Algorithm::SHA256,
+            Algorithm::Sha384 => lesspass::Algorithm::SHA384,
+            Algorithm::Sha512 => lesspass::Algorithm::SHA512,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sha{}", self.digest_size())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha384" => Ok(Algorithm::Sha384),
+            "sha512" => Ok(Algorithm::Sha512),
+            _ => Err(format!(
+                "unknown algorithm '{}', expected sha256, sha384 or sha512",
+                s
+            )),
+        }
+    }
+}
+
+// Define an enum of the ways the entropy is rendered
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum Mode {
+    #[default]
+    Password,
+    Passphrase {
+        words: usize,
+        separator: String,
+        capitalize: bool,
+    },
+    Pin,
+    Mnemonic {
+        words: usize,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PasswordOptions {
+    pub domain: String,
+    pub login: String,
+    pub master_password: String,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+    pub length: usize,
+    pub counter: u32,
+    pub version: u32,
+    pub algorithm: Algorithm,
+    pub iterations: u32,
+    pub excluded_characters: String,
+    pub allowed_symbols: Option<String>,
+    pub no_lookalikes: bool,
+    pub sub_counter: u32,
+    pub mode: Mode,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        Self {
+            domain: String::new(),
+            login: String::new(),
+            master_password: String::new(),
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            length: 16,
+            counter: 1,
+            version: 2,
+            algorithm: Algorithm::Sha256,
+            iterations: DEFAULT_ITERATIONS,
+            excluded_characters: String::new(),
+            allowed_symbols: None,
+            no_lookalikes: false,
+            sub_counter: 0,
+            mode: Mode::Password,
+        }
+    }
+}
+
+impl PasswordOptions {
+    // Returns whether the password needs the custom character set renderer
+    pub fn has_custom_characters(&self) -> bool {
+        !self.excluded_characters.is_empty() || self.allowed_symbols.is_some() || self.no_lookalikes
+    }
+}
+
+/*
+    Generates a password based on the provided options.
+
+    # Arguments
+
+    * `options` - A `PasswordOptions` struct containing parameters for generating the password.
+
+    # Returns
+
+    A string representing the generated password.
+*/
+pub fn generate_password(options: PasswordOptions) -> String {
+    if options.version == 1 {
+        return generate_password_v1(&options);
+    }
+
+    let entropy = derive_entropy(&options);
+    render_entropy(
+        &options,
+        &sub_counter_entropy(&entropy, options.sub_counter),
+    )
+}
+
+// Derives the LessPass entropy of the options with PBKDF2
+pub(crate) fn derive_entropy(options: &PasswordOptions) -> Vec<u8> {
+    let salt = lesspass::generate_salt(&options.domain, &options.login, options.counter);
+    let entropy = lesspass::generate_entropy(
+        &options.master_password,
+        &salt,
+        options.algorithm.to_lesspass(),
+        options.iterations,
+    );
+    entropy[..ENTROPY_LENGTH].to_vec()
+}
+
+// Re-keys the entropy for a non-zero sub-counter, leaving it unchanged for zero
+pub(crate) fn sub_counter_entropy(entropy: &[u8], sub_counter: u32) -> Vec<u8> {
+    if sub_counter == 0 {
+        return entropy.to_vec();
+    }
+    let mut mac = Hmac::<Sha256>::new_from_slice(entropy).expect("HMAC accepts keys of any length");
+    mac.update(SUB_COUNTER_TAG);
+    mac.update(&sub_counter.to_be_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Renders the entropy as a passphrase, a PIN, a mnemonic, or with the character sets of the options
+pub(crate) fn render_entropy(options: &PasswordOptions, entropy: &[u8]) -> String {
+    match options.mode {
+        Mode::Passphrase {
+            words,
+            ref separator,
+            capitalize,
+        } => return render_passphrase(entropy, words, separator, capitalize),
+        Mode::Pin => return render_pin(entropy, options.length),
+        Mode::Mnemonic { words } => return render_mnemonic(entropy, words),
+        Mode::Password => {}
+    }
+    if options.has_custom_characters() {
+        let classes = render::character_classes(options);
+        return render::render_password(entropy, &classes, options.length);
+    }
+
+    let charset = generate_charset(
+        options.lowercase,
+        options.uppercase,
+        options.digits,
+        options.symbols,
+    );
+    lesspass::render_password(entropy, charset, options.length)
+}
+
+/*
+    Generates a character set based on the provided options.
+
+    # Arguments
+
+    * `lowercase` - A boolean indicating whether to include lowercase characters.
+    * `uppercase` - A boolean indicating whether to include uppercase characters.
+    * `digits` - A boolean indicating whether to include digits.
+    * `symbols` - A boolean indicating whether to include symbols.
+
+    # Returns
+
+    A `CharacterSet` enum representing the generated character set.
+*/
+fn generate_charset(lowercase: bool, uppercase: bool, digits: bool, symbols: bool) -> CharacterSet {
+    let mut charset = CharacterSet::All;
+    if !lowercase {
+        charset.remove(CharacterSet::Lowercase);
+    }
+    if !uppercase {
+        charset.remove(CharacterSet::Uppercase);
+    }
+    if !digits {
+        charset.remove(CharacterSet::Numbers);
+    }
+    if !symbols {
+        charset.remove(CharacterSet::Symbols);
+    }
+    charset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_password_all() {
+        let options = PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            length: 16,
+            counter: 1,
+            ..PasswordOptions::default()
+        };
+
+        let password = generate_password(options);
+
+        assert_eq!(password, "fV1^3lS*'[knImg8");
+    }
+
+    #[test]
+    fn test_generate_password_lowercase() {
+        let options = PasswordOptions {
+            domain: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis.".to_string(),
+            login: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis.".to_string(),
+            master_password: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis.".to_string(),
+            lowercase: true,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+            length: 35,
+            counter: 100,
+            ..PasswordOptions::default()
+        };
+
+        let password = generate_password(options);
+
+        assert_eq!(password, "qxwsvaqanocacbfpvucqxphpcdajhjehoty");
+    }
+
+    #[test]
+    fn test_generate_password_digits_symbols() {
+        let options = PasswordOptions {
+            domain: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis. Eu consequat ac felis donec et odio pellentesque diam volutpat. Mi in nulla posuere sollicitudin. Euismod quis viverra nibh cras. Tristique nulla aliquet enim tortor at auctor urna nunc. Dignissim convallis aenean et tortor at. Turpis egestas pretium aenean pharetra. Sed vulputate odio ut enim. Faucibus et molestie ac feugiat. Donec ultrices tincidunt arcu non sodales neque sodales ut etiam. Donec pretium vulputate sapien nec sagittis aliquam malesuada. Mauris cursus mattis molestie a iaculis. Hendrerit gravida rutrum quisque non. Enim nulla aliquet porttitor lacus luctus accumsan tortor posuere. Et leo duis ut diam quam nulla. Quam lacus suspendisse faucibus interdum posuere lorem. Adipiscing elit ut aliquam purus sit amet. Consectetur adipiscing elit ut aliquam purus sit amet. Erat imperdiet sed euismod nisi porta lorem mollis.".to_string(),
+            login: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis. Eu consequat ac felis donec et odio pellentesque diam volutpat. Mi in nulla posuere sollicitudin. Euismod quis viverra nibh cras. Tristique nulla aliquet enim tortor at auctor urna nunc. Dignissim convallis aenean et tortor at. Turpis egestas pretium aenean pharetra. Sed vulputate odio ut enim. Faucibus et molestie ac feugiat. Donec ultrices tincidunt arcu non sodales neque sodales ut etiam. Donec pretium vulputate sapien nec sagittis aliquam malesuada. Mauris cursus mattis molestie a iaculis. Hendrerit gravida rutrum quisque non. Enim nulla aliquet porttitor lacus luctus accumsan tortor posuere. Et leo duis ut diam quam nulla. Quam lacus suspendisse faucibus interdum posuere lorem. Adipiscing elit ut aliquam purus sit amet. Consectetur adipiscing elit ut aliquam purus sit amet. Erat imperdiet sed euismod nisi porta lorem mollis.".to_string(),
+            master_password: "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Neque sodales ut etiam sit amet nisl purus in mollis. Eu consequat ac felis donec et odio pellentesque diam volutpat. Mi in nulla posuere sollicitudin. Euismod quis viverra nibh cras. Tristique nulla aliquet enim tortor at auctor urna nunc. Dignissim convallis aenean et tortor at. Turpis egestas pretium aenean pharetra. Sed vulputate odio ut enim. Faucibus et molestie ac feugiat. Donec ultrices tincidunt arcu non sodales neque sodales ut etiam. Donec pretium vulputate sapien nec sagittis aliquam malesuada. Mauris cursus mattis molestie a iaculis. Hendrerit gravida rutrum quisque non. Enim nulla aliquet porttitor lacus luctus accumsan tortor posuere. Et leo duis ut diam quam nulla. Quam lacus suspendisse faucibus interdum posuere lorem. Adipiscing elit ut aliquam purus sit amet. Consectetur adipiscing elit ut aliquam purus sit amet. Erat imperdiet sed euismod nisi porta lorem mollis.".to_string(),
+            lowercase: false,
+            uppercase: false,
+            digits: true,
+            symbols: true,
+            length: 27,
+            counter: 52,
+            ..PasswordOptions::default()
+        };
+
+        let password = generate_password(options);
+
+        assert_eq!(password, ")_*{$<=&>=8:>9):'*>7/83^#:}");
+    }
+
+    #[test]
+    fn test_generate_password_version_1() {
+        let options = PasswordOptions {
+            domain: "example.org".to_string(),
+            login: "contact@example.org".to_string(),
+            master_password: "password".to_string(),
+            length: 12,
+            version: 1,
+            ..PasswordOptions::default()
+        };
+
+        let password = generate_password(options);
+
+        assert_eq!(password, "izIS5@ozYM2?");
+    }
+
+    #[test]
+    fn test_default_kdf_reproduces_vectors() {
+        let options = PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            algorithm: Algorithm::Sha256,
+            iterations: 100000,
+            ..PasswordOptions::default()
+        };
+
+        assert_eq!(PasswordOptions::default().algorithm, Algorithm::Sha256);
+        assert_eq!(PasswordOptions::default().iterations, DEFAULT_ITERATIONS);
+        assert_eq!(generate_password(options), "fV1^3lS*'[knImg8");
+    }
+
+    #[test]
+    fn test_generate_password_algorithms() {
+        let options = |algorithm, iterations| PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            algorithm,
+            iterations,
+            ..PasswordOptions::default()
+        };
+
+        assert_eq!(
+            generate_password(options(Algorithm::Sha384, 100000)),
+            "=1KuD72z@EtZCwpQ"
+        );
+        assert_eq!(
+            generate_password(options(Algorithm::Sha512, 100000)),
+            "^aqiM!GwRqy9r7&~"
+        );
+        assert_eq!(
+            generate_password(options(Algorithm::Sha256, 200000)),
+            "Oc1-7_MlJB/V0Ew)"
+        );
+    }
+
+    #[test]
+    fn test_algorithm_conversions() {
+        for algorithm in [Algorithm::Sha256, Algorithm::Sha384, Algorithm::Sha512] {
+            assert_eq!(
+                Algorithm::from_digest_size(algorithm.digest_size()),
+                algorithm
+            );
+            assert_eq!(algorithm.to_string().parse::<Algorithm>(), Ok(algorithm));
+        }
+        assert_eq!(Algorithm::from_digest_size(0), Algorithm::Sha256);
+        assert!("md5".parse::<Algorithm>().is_err());
+    }
+
+    #[test]
+    fn test_generate_password_custom_characters() {
+        let options = PasswordOptions {
+            domain: "example.org".to_string(),
+            login: "contact@example.org".to_string(),
+            master_password: "password".to_string(),
+            allowed_symbols: Some("-_".to_string()),
+            ..PasswordOptions::default()
+        };
+
+        assert!(options.has_custom_characters());
+        assert_eq!(generate_password(options), "ES-XSsZQ_9bGf7D6");
+    }
+
+    #[test]
+    fn test_generate_password_no_lookalikes() {
+        let options = |no_lookalikes| PasswordOptions {
+            domain: "example.org".to_string(),
+            login: "contact@example.org".to_string(),
+            master_password: "password".to_string(),
+            no_lookalikes,
+            ..PasswordOptions::default()
+        };
+
+        // Profiles without the option keep the LessPass passwords
+        assert!(!options(false).has_custom_characters());
+        assert_eq!(generate_password(options(false)), "WHLpUL)e00[iHR+w");
+        assert_eq!(generate_password(options(true)), "zzK7$/&\"<Yd#JEEA");
+    }
+
+    #[test]
+    fn test_sub_counter_changes_password() {
+        let options = |sub_counter| PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            sub_counter,
+            ..PasswordOptions::default()
+        };
+
+        assert_eq!(generate_password(options(0)), "fV1^3lS*'[knImg8");
+        assert_ne!(generate_password(options(1)), "fV1^3lS*'[knImg8");
+        assert_eq!(generate_password(options(1)), generate_password(options(1)));
+    }
+
+    #[test]
+    fn test_generate_passphrase() {
+        let options = PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            mode: Mode::Passphrase {
+                words: 6,
+                separator: "-".to_string(),
+                capitalize: true,
+            },
+            ..PasswordOptions::default()
+        };
+
+        let passphrase = generate_password(options);
+
+        assert_eq!(passphrase, "Carport-Sanding-Crazed-Ahoy-Liability-Discolor");
+    }
+
+    #[test]
+    fn test_generate_pin() {
+        let options = PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            length: 6,
+            mode: Mode::Pin,
+            ..PasswordOptions::default()
+        };
+
+        assert_eq!(generate_password(options), "079683");
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        let options = |words| PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            mode: Mode::Mnemonic { words },
+            ..PasswordOptions::default()
+        };
+
+        assert_eq!(
+            generate_password(options(12)),
+            "call blood funny town dash peanut worry demise change tail border mushroom"
+        );
+        assert_eq!(
+            generate_password(options(24)),
+            "call blood funny town dash peanut worry demise change tail border mother elegant object secret sibling decrease sick jaguar regular crack theory toe tool"
+        );
+    }
+}
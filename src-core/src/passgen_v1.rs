@@ -0,0 +1,195 @@
+/*
+    This module contains the LessPass v1 (legacy) password generation algorithm.
+
+    LessPass v1 encrypts the login with PBKDF2-SHA256 (8192 iterations), derives an HMAC-SHA256
+    of the site and counter keyed with the encrypted login, and renders the first `length` hex
+    characters of that HMAC through a template of vowels, consonants, digits and symbols.
+
+    The file also includes unit tests using the vectors of the upstream LessPass test suite.
+*/
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt::Write;
+
+use crate::passgen::PasswordOptions;
+
+pub const ITERATIONS: u32 = 8192;
+
+const VOWELS_UPPERCASE: &str = "AEIOUY";
+const CONSONANTS_UPPERCASE: &str = "BCDFGHJKLMNPQRSTVWXZ";
+const VOWELS_LOWERCASE: &str = "aeiouy";
+const CONSONANTS_LOWERCASE: &str = "bcdfghjklmnpqrstvwxz";
+const NUMBERS: &str = "0123456789";
+const SYMBOLS: &str = "@&%?,=[]_:-+*$#!'^~;()/.";
+
+/*
+    Generates a LessPass v1 password based on the provided options.
+
+    # Arguments
+
+    * `options` - A reference to the `PasswordOptions` used to generate the password.
+
+    # Returns
+
+    A string representing the generated password.
+*/
+pub fn generate_password_v1(options: &PasswordOptions) -> String {
+    let encrypted_login = encrypt_login(&options.login, &options.master_password, ITERATIONS);
+    let derived_login = derive_encrypted_login(
+        &encrypted_login,
+        &options.domain,
+        options.counter,
+        options.length,
+    );
+    pretty_print(&derived_login, &password_template(options))
+}
+
+// Encrypts the login with the master password, as a lowercase hex string
+fn encrypt_login(login: &str, master_password: &str, iterations: u32) -> String {
+    let encrypted_login = lesspass::generate_entropy(
+        master_password,
+        login.as_bytes(),
+        lesspass::Algorithm::SHA256,
+        iterations,
+    );
+    to_hex(&encrypted_login)
+}
+
+// Derives the site specific hash and keeps its first `length` hex characters
+fn derive_encrypted_login(
+    encrypted_login: &str,
+    site: &str,
+    counter: u32,
+    length: usize,
+) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(encrypted_login.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(format!("{}{}", site, counter).as_bytes());
+    let derived_hash = to_hex(&mac.finalize().into_bytes());
+    derived_hash.chars().take(length).collect()
+}
+
+// Builds the rendering template, e.g. "vcVCns" when every character set is enabled
+fn password_template(options: &PasswordOptions) -> String {
+    let mut template = String::new();
+    if options.lowercase {
+        template.push_str("vc");
+    }
+    if options.uppercase {
+        template.push_str("VC");
+    }
+    if options.digits {
+        template.push('n');
+    }
+    if options.symbols {
+        template.push('s');
+    }
+    template
+}
+
+// Renders each character of the hash with the character class of the template
+fn pretty_print(hash: &str, template: &str) -> String {
+    let template: Vec<char> = template.chars().collect();
+    if template.is_empty() {
+        return String::new();
+    }
+
+    hash.chars()
+        .enumerate()
+        .map(|(index, character)| {
+            let characters = match template[index % template.len()] {
+                'V' => VOWELS_UPPERCASE,
+                'C' => CONSONANTS_UPPERCASE,
+                'v' => VOWELS_LOWERCASE,
+                'c' => CONSONANTS_LOWERCASE,
+                'n' => NUMBERS,
+                _ => SYMBOLS,
+            };
+            let code = character as usize;
+            characters
+                .chars()
+                .nth(code % characters.len())
+                .expect("index within the character class")
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut acc, &byte| {
+        write!(acc, "{:02x}", byte).expect("Failed to write to string");
+        acc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(site: &str, login: &str, length: usize, counter: u32) -> PasswordOptions {
+        PasswordOptions {
+            domain: site.to_string(),
+            login: login.to_string(),
+            master_password: "password".to_string(),
+            length,
+            counter,
+            version: 1,
+            ..PasswordOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_password_v1_upstream_example_org() {
+        let password = generate_password_v1(&options("example.org", "contact@example.org", 12, 1));
+
+        assert_eq!(password, "izIS5@ozYM2?");
+    }
+
+    #[test]
+    fn test_generate_password_v1_upstream_lesspass_com() {
+        let password =
+            generate_password_v1(&options("lesspass.com", "contact@lesspass.com", 12, 1));
+
+        assert_eq!(password, "azYS7,olOL2]");
+    }
+
+    #[test]
+    fn test_generate_password_v1_lowercase() {
+        let password = generate_password_v1(&PasswordOptions {
+            uppercase: false,
+            digits: false,
+            symbols: false,
+            ..options("example.org", "contact@example.org", 14, 2)
+        });
+
+        assert_eq!(password, "ybucanawunotuq");
+    }
+
+    #[test]
+    fn test_generate_password_v1_digits() {
+        let password = generate_password_v1(&PasswordOptions {
+            lowercase: false,
+            uppercase: false,
+            symbols: false,
+            ..options("example.org", "contact@example.org", 6, 1)
+        });
+
+        assert_eq!(password, "090458");
+    }
+
+    #[test]
+    fn test_generate_password_v1_length_capped_by_hash() {
+        let password = generate_password_v1(&options("example.org", "contact@example.org", 100, 1));
+
+        assert_eq!(password.len(), 64);
+        assert!(password.starts_with("izIS5@ozYM2?"));
+    }
+
+    #[test]
+    fn test_password_template() {
+        assert_eq!(
+            password_template(&options("example.org", "contact@example.org", 12, 1)),
+            "vcVCns"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/*
+    This module contains the rendering of passwords as passphrases, such as
+    "Carport-Sanding-Crazed-Ahoy-Liability-Discolor", which are easier to type on TVs and at
+    disk encryption prompts.
+
+    The `render_passphrase` function maps the LessPass entropy onto the EFF large wordlist
+    (7776 words, about 12.9 bits per word), picking every word with the same big-integer
+    division LessPass uses for characters. Replacing the list changes every generated passphrase.
+
+    The file also includes unit tests using fixed entropy.
+*/
+
+use crate::render::divmod;
+use crate::wordlists::eff_large_words;
+
+pub const DEFAULT_WORDS: usize = 6;
+pub const DEFAULT_SEPARATOR: &str = "-";
+
+// Passphrases are limited to the words the 256 bits of entropy can pick
+pub const MAX_WORDS: usize = 19;
+
+/*
+    Renders a passphrase from the entropy.
+
+    Arguments:
+    - `entropy`: A slice of bytes holding the big-endian entropy.
+    - `words`: The number of words, capped to `MAX_WORDS`.
+    - `separator`: A string slice inserted between the words.
+    - `capitalize`: A boolean indicating whether to capitalize the first letter of every word.
+
+    Returns:
+    A string representing the rendered passphrase.
+*/
+pub fn render_passphrase(
+    entropy: &[u8],
+    words: usize,
+    separator: &str,
+    capitalize: bool,
+) -> String {
+    let wordlist = eff_large_words();
+    let mut quotient = entropy.to_vec();
+
+    (0..words.min(MAX_WORDS))
+        .map(|_| {
+            let word = wordlist[divmod(&mut quotient, wordlist.len())];
+            if capitalize {
+                capitalize_word(word)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn capitalize_word(word: &str) -> String {
+    let mut characters = word.chars();
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_passphrase_picks_words_by_division() {
+        // 7777 = 1 * 7776 + 1, so the first word is the second of the list, then the second one
+        let entropy = [0x1e, 0x61];
+
+        assert_eq!(
+            render_passphrase(&entropy, 3, " ", false),
+            "abdomen abdomen abacus"
+        );
+    }
+
+    #[test]
+    fn test_render_passphrase_options() {
+        let entropy = [0xff; 32];
+
+        let passphrase = render_passphrase(&entropy, 4, ".", true);
+
+        assert_eq!(passphrase.split('.').count(), 4);
+        assert!(passphrase
+            .split('.')
+            .all(|word| word.starts_with(|c: char| c.is_ascii_uppercase())));
+        assert_eq!(
+            render_passphrase(&entropy, 100, "-", false)
+                .split('-')
+                .count(),
+            MAX_WORDS
+        );
+    }
+}
@@ -0,0 +1,151 @@
+/*
+    This module contains the rendering of passwords as PIN codes for phones and door keypads.
+
+    The `render_pin` function derives numbered candidates from the LessPass entropy and keeps
+    the first one that is not weak, so the same inputs always give the same PIN.
+    The `is_weak_pin` function rejects common PINs, sequences, runs, repeated blocks and PINs
+    made of fewer than three different digits.
+
+    The file also includes unit tests with reproducible vectors.
+*/
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::render::divmod;
+
+pub const MIN_PIN_LENGTH: usize = 4;
+pub const MAX_PIN_LENGTH: usize = 12;
+
+// Domain separation tag of the PIN candidates
+const CANDIDATE_TAG: &[u8] = b"rustedlesspass-pin";
+
+// Most common PINs, from the analyses of leaked PIN datasets
+const BLOCKLIST: [&str; 33] = [
+    "1234", "1111", "0000", "1212", "7777", "1004", "2000", "4444", "2222", "6969", "9999", "3333",
+    "5555", "6666", "1122", "1313", "8888", "4321", "2001", "1010", "123123", "112233", "121212",
+    "654321", "159753", "147258", "123321", "696969", "111222", "102030", "789456", "147852",
+    "258369",
+];
+
+/*
+    Renders a PIN code from the entropy.
+
+    Arguments:
+    - `entropy`: A slice of bytes holding the big-endian entropy.
+    - `length`: The number of digits, clamped between `MIN_PIN_LENGTH` and `MAX_PIN_LENGTH`.
+
+    Returns:
+    A string representing the first candidate PIN that is not weak.
+*/
+pub fn render_pin(entropy: &[u8], length: usize) -> String {
+    let length = length.clamp(MIN_PIN_LENGTH, MAX_PIN_LENGTH);
+    (0..)
+        .map(|index| candidate(entropy, index, length))
+        .find(|pin| !is_weak_pin(pin))
+        .expect("a PIN that is not weak")
+}
+
+/*
+    Checks whether a PIN code is easy to guess.
+
+    Arguments:
+    - `pin`: A string slice containing the digits of the PIN.
+
+    Returns:
+    `true` if the PIN is not only made of digits, is blocklisted, is a sequence, contains three identical digits in a row,
+    repeats a shorter block, or uses fewer than three different digits.
+*/
+pub fn is_weak_pin(pin: &str) -> bool {
+    if !pin.bytes().all(|byte| byte.is_ascii_digit()) {
+        return true;
+    }
+    let digits: Vec<i8> = pin.bytes().map(|byte| byte as i8 - b'0' as i8).collect();
+
+    BLOCKLIST.contains(&pin)
+        || is_sequence(&digits)
+        || digits
+            .windows(3)
+            .any(|run| run[0] == run[1] && run[1] == run[2])
+        || is_repeated_block(pin)
+        || distinct_digits(&digits) < 3
+}
+
+// Derives the numbered candidate PIN from the entropy
+fn candidate(entropy: &[u8], index: u32, length: usize) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(entropy).expect("HMAC accepts keys of any length");
+    mac.update(CANDIDATE_TAG);
+    mac.update(&index.to_be_bytes());
+    let mut quotient = mac.finalize().into_bytes().to_vec();
+
+    (0..length)
+        .map(|_| char::from(b'0' + divmod(&mut quotient, 10) as u8))
+        .collect()
+}
+
+// Checks for ascending or descending sequences such as 3456 or 9876
+fn is_sequence(digits: &[i8]) -> bool {
+    let steps: Vec<i8> = digits.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    steps.iter().all(|&step| step == 1) || steps.iter().all(|&step| step == -1)
+}
+
+// Checks for PINs repeating a shorter block, such as 1212 or 123123
+fn is_repeated_block(pin: &str) -> bool {
+    (1..=pin.len() / 2)
+        .filter(|&size| pin.len().is_multiple_of(size))
+        .any(|size| pin[..size].repeat(pin.len() / size) == pin)
+}
+
+fn distinct_digits(digits: &[i8]) -> usize {
+    let mut seen = [false; 10];
+    for &digit in digits {
+        seen[digit as usize] = true;
+    }
+    seen.iter().filter(|&&seen| seen).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entropy(counter: u32) -> Vec<u8> {
+        let salt = lesspass::generate_salt("lorem ipsum", "lorem ipsum", counter);
+        lesspass::generate_entropy("lorem ipsum", &salt, lesspass::Algorithm::SHA256, 100000)
+    }
+
+    #[test]
+    fn test_render_pin_vectors() {
+        let entropy = entropy(1);
+
+        assert_eq!(render_pin(&entropy, 4), "0796");
+        assert_eq!(render_pin(&entropy, 6), "079683");
+    }
+
+    #[test]
+    fn test_render_pin_skips_weak_candidates() {
+        let entropy = entropy(10);
+
+        assert_eq!(candidate(&entropy, 0, 4), "8448");
+        assert_eq!(render_pin(&entropy, 4), "1008");
+    }
+
+    #[test]
+    fn test_render_pin_clamps_length() {
+        let entropy = entropy(1);
+
+        assert_eq!(render_pin(&entropy, 1).len(), MIN_PIN_LENGTH);
+        assert_eq!(render_pin(&entropy, 35).len(), MAX_PIN_LENGTH);
+    }
+
+    #[test]
+    fn test_is_weak_pin() {
+        for pin in [
+            "1234", "0000", "111111", "123456", "987654", "1112", "2525", "123123",
+        ] {
+            assert!(is_weak_pin(pin), "{} should be weak", pin);
+        }
+        for pin in ["0796", "1008", "4331", "396320"] {
+            assert!(!is_weak_pin(pin), "{} should not be weak", pin);
+        }
+    }
+}
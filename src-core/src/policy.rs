@@ -0,0 +1,402 @@
+/*
+    This module contains the password policies enforced by some websites, such as
+    "8 to 16 characters, starting with a letter, with no more than 2 repeated characters".
+
+    The `Policy` struct declares the rules, and the `PRESETS` constant names the common ones.
+    The `generate_password_with_policy` function generates a password and, while the password
+    breaks the policy, bumps a sub-counter deriving new entropy from the same PBKDF2 result.
+    The sub-counter of the first valid password is returned so that it can be recorded in
+    the profile, which keeps the password reproducible.
+
+    The file also includes unit tests for the rules and the sub-counter search.
+*/
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::passgen::{
+    derive_entropy, generate_password, render_entropy, sub_counter_entropy, Mode, PasswordOptions,
+};
+use crate::pin::{MAX_PIN_LENGTH, MIN_PIN_LENGTH};
+
+// Number of sub-counters tried before giving up on a policy
+pub const MAX_SUB_COUNTER: u32 = 1000;
+
+// Define a struct holding the rules of a password policy
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Policy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub starts_with_letter: bool,
+    pub max_repeated: Option<usize>,
+}
+
+// Define an enum of the rules a password can break
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    TooShort,
+    TooLong,
+    DoesNotStartWithLetter,
+    TooManyRepeated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PolicyError {
+    UnsupportedMode,
+    LetterInPin,
+    LengthOutOfRange,
+    Unsatisfiable,
+}
+
+pub const PRESETS: [(&str, Policy); 3] = [
+    (
+        "bank",
+        Policy {
+            min_length: 8,
+            max_length: 16,
+            starts_with_letter: true,
+            max_repeated: Some(2),
+        },
+    ),
+    (
+        "mainframe",
+        Policy {
+            min_length: 6,
+            max_length: 8,
+            starts_with_letter: true,
+            max_repeated: Some(2),
+        },
+    ),
+    (
+        "no-repeats",
+        Policy {
+            min_length: 1,
+            max_length: 64,
+            starts_with_letter: false,
+            max_repeated: Some(1),
+        },
+    ),
+];
+
+/*
+    Returns the preset policy with the given name.
+
+    Arguments:
+    - `name`: A string slice representing the preset name, as stored in the settings.
+
+    Returns:
+    The matching `Policy`, or `None` for an empty or unknown name.
+*/
+pub fn preset(name: &str) -> Option<&'static Policy> {
+    PRESETS
+        .iter()
+        .find(|(preset_name, _)| *preset_name == name)
+        .map(|(_, policy)| policy)
+}
+
+impl Policy {
+    /*
+        Checks a password against the rules of the policy.
+
+        Arguments:
+        - `password`: A string slice representing the password.
+
+        Returns:
+        A vector with every rule the password breaks, empty for a valid password.
+    */
+    pub fn violations(&self, password: &str) -> Vec<Violation> {
+        let characters: Vec<char> = password.chars().collect();
+        let mut violations = Vec::new();
+
+        if characters.len() < self.min_length {
+            violations.push(Violation::TooShort);
+        }
+        if characters.len() > self.max_length {
+            violations.push(Violation::TooLong);
+        }
+        if self.starts_with_letter
+            && !characters
+                .first()
+                .is_some_and(|character| character.is_ascii_alphabetic())
+        {
+            violations.push(Violation::DoesNotStartWithLetter);
+        }
+        if let Some(max_repeated) = self.max_repeated {
+            if longest_run(&characters) > max_repeated {
+                violations.push(Violation::TooManyRepeated);
+            }
+        }
+
+        violations
+    }
+
+    // Returns whether a password of this length can satisfy the policy
+    fn accepts_length(&self, length: usize) -> bool {
+        (self.min_length..=self.max_length).contains(&length)
+    }
+
+    /*
+        Tells whether a PIN, made of digits only, can satisfy the policy.
+
+        Returns:
+        `false` if the policy requires a leading letter.
+    */
+    pub fn accepts_pins(&self) -> bool {
+        !self.starts_with_letter
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} to {} characters", self.min_length, self.max_length)?;
+        if self.starts_with_letter {
+            write!(f, ", starting with a letter")?;
+        }
+        match self.max_repeated {
+            Some(1) => write!(f, ", no repeated characters"),
+            Some(max_repeated) => write!(f, ", at most {} repeated characters", max_repeated),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::UnsupportedMode => {
+                write!(f, "Policies only apply to passwords and PINs")
+            }
+            PolicyError::LetterInPin => {
+                write!(f, "A PIN cannot start with a letter as the policy requires")
+            }
+            PolicyError::LengthOutOfRange => {
+                write!(f, "The password length is not allowed by the policy")
+            }
+            PolicyError::Unsatisfiable => {
+                write!(f, "Unable to generate a password matching the policy")
+            }
+        }
+    }
+}
+
+/*
+    Generates a password matching the policy.
+
+    The sub-counter of the options is tried first, then the following ones until a password
+    matches the policy. LessPass v1 passwords have no sub-counter and are only checked.
+    Passphrases and mnemonics are rejected, as their length is not the one of the options,
+    and so are PINs for a policy requiring a leading letter.
+
+    Arguments:
+    - `options`: A `PasswordOptions` struct containing parameters for generating the password.
+    - `policy`: A reference to the `Policy` the password must match.
+
+    Returns:
+    The password and the sub-counter it was generated with, or a `PolicyError`.
+*/
+pub fn generate_password_with_policy(
+    options: PasswordOptions,
+    policy: &Policy,
+) -> Result<(String, u32), PolicyError> {
+    if !matches!(options.mode, Mode::Password | Mode::Pin) {
+        return Err(PolicyError::UnsupportedMode);
+    }
+    if options.mode == Mode::Pin && !policy.accepts_pins() {
+        return Err(PolicyError::LetterInPin);
+    }
+    // The length of a PIN is clamped when it is rendered
+    let length = match options.mode {
+        Mode::Pin => options.length.clamp(MIN_PIN_LENGTH, MAX_PIN_LENGTH),
+        _ => options.length,
+    };
+    if !policy.accepts_length(length) {
+        return Err(PolicyError::LengthOutOfRange);
+    }
+
+    if options.version == 1 {
+        let password = generate_password(options);
+        if !policy.violations(&password).is_empty() {
+            return Err(PolicyError::Unsatisfiable);
+        }
+        return Ok((password, 0));
+    }
+
+    let entropy = derive_entropy(&options);
+    (options.sub_counter..=MAX_SUB_COUNTER)
+        .map(|sub_counter| {
+            let password = render_entropy(&options, &sub_counter_entropy(&entropy, sub_counter));
+            (password, sub_counter)
+        })
+        .find(|(password, _)| policy.violations(password).is_empty())
+        .ok_or(PolicyError::Unsatisfiable)
+}
+
+// Returns the length of the longest run of identical characters
+fn longest_run(characters: &[char]) -> usize {
+    characters
+        .chunk_by(|a, b| a == b)
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lorem_ipsum() -> PasswordOptions {
+        PasswordOptions {
+            domain: "lorem ipsum".to_string(),
+            login: "lorem ipsum".to_string(),
+            master_password: "lorem ipsum".to_string(),
+            ..PasswordOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_violations() {
+        let policy = preset("bank").unwrap();
+
+        assert_eq!(policy.violations("abcdefgh"), vec![]);
+        assert_eq!(policy.violations("abc"), vec![Violation::TooShort]);
+        assert_eq!(
+            policy.violations("1bcdefghijklmnopq"),
+            vec![Violation::TooLong, Violation::DoesNotStartWithLetter]
+        );
+        assert_eq!(
+            policy.violations("abcccdef"),
+            vec![Violation::TooManyRepeated]
+        );
+        assert_eq!(policy.violations("abccdeff"), vec![]);
+    }
+
+    #[test]
+    fn test_preset() {
+        assert_eq!(preset("mainframe").unwrap().max_length, 8);
+        assert_eq!(preset(""), None);
+        assert_eq!(preset("unknown"), None);
+    }
+
+    #[test]
+    fn test_policy_display() {
+        assert_eq!(
+            preset("bank").unwrap().to_string(),
+            "8 to 16 characters, starting with a letter, at most 2 repeated characters"
+        );
+        assert_eq!(
+            preset("no-repeats").unwrap().to_string(),
+            "1 to 64 characters, no repeated characters"
+        );
+    }
+
+    #[test]
+    fn test_valid_password_keeps_sub_counter() {
+        let policy = preset("no-repeats").unwrap();
+
+        let result = generate_password_with_policy(lorem_ipsum(), policy);
+
+        assert_eq!(result, Ok(("fV1^3lS*'[knImg8".to_string(), 0)));
+    }
+
+    #[test]
+    fn test_invalid_password_bumps_sub_counter() {
+        // The passwords of the sub-counters 0 and 1 do not start with a letter
+        let options = PasswordOptions {
+            counter: 2,
+            ..lorem_ipsum()
+        };
+        let policy = preset("bank").unwrap();
+
+        let (password, sub_counter) =
+            generate_password_with_policy(options.clone(), policy).unwrap();
+
+        assert_eq!(sub_counter, 2);
+        assert_eq!(password, "h$Q{Up\"IWKB1Gewm");
+        assert_eq!(
+            generate_password(PasswordOptions {
+                sub_counter,
+                ..options.clone()
+            }),
+            password
+        );
+        assert_eq!(
+            generate_password_with_policy(
+                PasswordOptions {
+                    sub_counter,
+                    ..options
+                },
+                policy
+            ),
+            Ok((password, sub_counter))
+        );
+    }
+
+    #[test]
+    fn test_length_out_of_range() {
+        let options = PasswordOptions {
+            length: 17,
+            ..lorem_ipsum()
+        };
+
+        assert_eq!(
+            generate_password_with_policy(options, preset("bank").unwrap()),
+            Err(PolicyError::LengthOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_passphrase_is_unsupported() {
+        let options = PasswordOptions {
+            mode: Mode::Passphrase {
+                words: 6,
+                separator: "-".to_string(),
+                capitalize: false,
+            },
+            ..lorem_ipsum()
+        };
+
+        assert_eq!(
+            generate_password_with_policy(options, preset("bank").unwrap()),
+            Err(PolicyError::UnsupportedMode)
+        );
+    }
+
+    #[test]
+    fn test_pin_policies() {
+        let pin = PasswordOptions {
+            mode: Mode::Pin,
+            length: 6,
+            ..lorem_ipsum()
+        };
+        let short_pin = PasswordOptions {
+            length: 2,
+            ..pin.clone()
+        };
+
+        assert_eq!(
+            generate_password_with_policy(pin.clone(), preset("bank").unwrap()),
+            Err(PolicyError::LetterInPin)
+        );
+        assert!(generate_password_with_policy(pin, preset("no-repeats").unwrap()).is_ok());
+        // A PIN is never shorter than 4 digits, which the policy is checked against
+        let (short, _) =
+            generate_password_with_policy(short_pin, preset("no-repeats").unwrap()).unwrap();
+        assert_eq!(short.len(), MIN_PIN_LENGTH);
+    }
+
+    #[test]
+    fn test_unsatisfiable_policy() {
+        let options = PasswordOptions {
+            lowercase: false,
+            uppercase: false,
+            symbols: false,
+            ..lorem_ipsum()
+        };
+
+        assert_eq!(
+            generate_password_with_policy(options, preset("bank").unwrap()),
+            Err(PolicyError::Unsatisfiable)
+        );
+    }
+}
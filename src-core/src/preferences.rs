@@ -0,0 +1,83 @@
+/*
+   This module defines a Preferences struct for the settings of the device rather than of
+   a site: the fingerprint display, the clipboard and reveal timeouts, and the normalization
+   of the website. They are stored apart from the profiles, so a profile never overrides
+   them and an export never carries them.
+
+   The `normalize` method normalizes a website to its domain when the preferences ask for it.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::normalize_site;
+
+// Seconds offered before a copied password is cleared from the clipboard, 0 to keep it
+pub const CLIPBOARD_TIMEOUTS: [u64; 5] = [0, 10, 30, 60, 120];
+pub const DEFAULT_CLIPBOARD_TIMEOUT: u64 = 30;
+
+// Seconds offered before a revealed password is masked again, 0 to keep it revealed
+pub const REVEAL_TIMEOUTS: [u64; 4] = [0, 10, 30, 60];
+pub const DEFAULT_REVEAL_TIMEOUT: u64 = 30;
+
+// Define a struct to hold the preferences of the device
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Preferences {
+    // Number of icons of the colored fingerprint, 0 for the LessPass one
+    pub rich_fingerprint: u64,
+    // Whether the fingerprint is also shown as words and emoji
+    pub text_fingerprint: u64,
+    pub clipboard_timeout: u64,
+    pub reveal_timeout: u64,
+    // Whether the website is normalized to its domain, 0 to use it as typed like LessPass
+    pub normalize_domain: u64,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            rich_fingerprint: 0,
+            text_fingerprint: 0,
+            clipboard_timeout: DEFAULT_CLIPBOARD_TIMEOUT,
+            reveal_timeout: DEFAULT_REVEAL_TIMEOUT,
+            normalize_domain: 0,
+        }
+    }
+}
+
+impl Preferences {
+    /*
+        Normalizes a website to its domain, if enabled.
+
+        Arguments:
+        - `website`: A string slice representing the website as typed.
+
+        Returns:
+        The registrable domain of the website, or the website itself if the normalization
+        is disabled.
+    */
+    pub fn normalize(&self, website: &str) -> String {
+        if self.normalize_domain != 0 {
+            normalize_site(website)
+        } else {
+            website.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let url = "https://accounts.google.com/signin?x=1";
+        let preferences = Preferences {
+            normalize_domain: 1,
+            ..Preferences::default()
+        };
+
+        assert_eq!(Preferences::default().normalize(url), url);
+        assert_eq!(preferences.normalize(url), "google.com");
+    }
+}
@@ -0,0 +1,191 @@
+/*
+   This module defines a Profiles store that remembers the password generation
+   settings used for each website/login pair.
+
+   A profile only holds the `Settings` (size, counter and character sets) of a
+   site: the master password and the generated passwords are never persisted.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+// Define a struct to hold the settings of a website/login pair
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Profile {
+    pub site: String,
+    pub login: String,
+    pub settings: Settings,
+}
+
+// Define a struct to hold every saved profile
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct Profiles {
+    pub entries: Vec<Profile>,
+}
+
+impl Profiles {
+    /*
+        Looks up the profile saved for a website/login pair.
+
+        Arguments:
+        - `site`: A string slice representing the website domain.
+        - `login`: A string slice representing the username.
+
+        Returns:
+        The matching `Profile`, if any.
+    */
+    pub fn find(&self, site: &str, login: &str) -> Option<&Profile> {
+        self.entries
+            .iter()
+            .find(|profile| profile.site == site && profile.login == login)
+    }
+
+    /*
+        Looks up the settings to generate the passwords of a website/login pair with.
+
+        Arguments:
+        - `site`: A string slice representing the website domain.
+        - `login`: A string slice representing the username.
+        - `defaults`: A reference to the `Settings` used when no profile is saved for this pair.
+
+        Returns:
+        The settings of the matching profile, or a copy of `defaults` without its policy, as the
+        policy and its sub-counter belong to the site they were set for.
+    */
+    pub fn settings_for(&self, site: &str, login: &str, defaults: &Settings) -> Settings {
+        self.find(site, login).map_or_else(
+            || Settings {
+                policy: String::new(),
+                sub_counter: 0,
+                ..defaults.clone()
+            },
+            |profile| profile.settings.clone(),
+        )
+    }
+
+    /*
+        Saves the settings of a website/login pair, replacing any previous profile.
+
+        Arguments:
+        - `site`: A string slice representing the website domain.
+        - `login`: A string slice representing the username.
+        - `settings`: A reference to the `Settings` to remember for this pair.
+    */
+    pub fn save(&mut self, site: &str, login: &str, settings: &Settings) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|profile| profile.site == site && profile.login == login)
+        {
+            Some(profile) => profile.settings = settings.clone(),
+            None => self.entries.push(Profile {
+                site: site.to_string(),
+                login: login.to_string(),
+                settings: settings.clone(),
+            }),
+        }
+    }
+
+    /*
+        Removes the profile saved for a website/login pair.
+
+        Returns:
+        `true` if a profile was removed.
+    */
+    pub fn remove(&mut self, site: &str, login: &str) -> bool {
+        let len = self.entries.len();
+        self.entries
+            .retain(|profile| !(profile.site == site && profile.login == login));
+        self.entries.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_settings() -> Settings {
+        Settings {
+            size: 20,
+            counter: 3,
+            symbols: 0,
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn test_find_missing_profile() {
+        let profiles = Profiles::default();
+
+        assert_eq!(profiles.find("example.org", "contact@example.org"), None);
+    }
+
+    #[test]
+    fn test_save_and_find_profile() {
+        let mut profiles = Profiles::default();
+        profiles.save("example.org", "contact@example.org", &custom_settings());
+
+        let profile = profiles
+            .find("example.org", "contact@example.org")
+            .expect("profile saved");
+
+        assert_eq!(profile.settings, custom_settings());
+        assert_eq!(profiles.find("example.org", "other@example.org"), None);
+    }
+
+    #[test]
+    fn test_settings_for_falls_back_to_defaults() {
+        let mut profiles = Profiles::default();
+        profiles.save("example.org", "contact@example.org", &custom_settings());
+        let defaults = Settings {
+            size: 12,
+            ..Settings::default()
+        };
+
+        assert_eq!(
+            profiles.settings_for("example.org", "contact@example.org", &defaults),
+            custom_settings()
+        );
+        // The settings of another site do not carry over to a site without a profile
+        assert_eq!(
+            profiles.settings_for("example.com", "contact@example.org", &defaults),
+            defaults
+        );
+    }
+
+    #[test]
+    fn test_settings_for_drops_the_default_policy() {
+        let profiles = Profiles::default();
+        let defaults = Settings {
+            policy: "bank".to_string(),
+            sub_counter: 3,
+            ..Settings::default()
+        };
+
+        assert_eq!(
+            profiles.settings_for("example.com", "contact@example.org", &defaults),
+            Settings::default()
+        );
+    }
+
+    #[test]
+    fn test_save_replaces_existing_profile() {
+        let mut profiles = Profiles::default();
+        profiles.save("example.org", "contact@example.org", &Settings::default());
+        profiles.save("example.org", "contact@example.org", &custom_settings());
+
+        assert_eq!(profiles.entries.len(), 1);
+        assert_eq!(profiles.entries[0].settings, custom_settings());
+    }
+
+    #[test]
+    fn test_remove_profile() {
+        let mut profiles = Profiles::default();
+        profiles.save("example.org", "contact@example.org", &custom_settings());
+
+        assert!(profiles.remove("example.org", "contact@example.org"));
+        assert!(!profiles.remove("example.org", "contact@example.org"));
+        assert!(profiles.entries.is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+/*
+    This module contains helpers to read a generated password out, for typing it by hand on
+    another device.
+
+    The `chunk_password` function splits a password into short groups of characters.
+    The `character_class` function tells the class of a character, to color it.
+    The `ambiguous_name` function names the characters that look alike in many fonts.
+    The `spell_character` and `spell_password` functions spell characters with the NATO
+    phonetic alphabet, and symbols with their names.
+
+    The file also includes unit tests for the classes and the spelling.
+*/
+
+use crate::render::{DIGITS, LOWERCASE, UPPERCASE};
+
+pub const CHUNK_SIZE: usize = 4;
+
+// Define an enum of the character classes of a password
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    Symbol,
+}
+
+// Characters easily mistaken for one another, with an unambiguous name
+const AMBIGUOUS: [(char, &str); 9] = [
+    ('0', "digit zero"),
+    ('O', "capital letter O"),
+    ('o', "small letter o"),
+    ('1', "digit one"),
+    ('l', "small letter L"),
+    ('I', "capital letter I"),
+    ('|', "vertical bar"),
+    ('\'', "apostrophe"),
+    ('`', "backtick"),
+];
+
+const NATO_ALPHABET: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+const DIGIT_NAMES: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+];
+
+const SYMBOL_NAMES: [(char, &str); 32] = [
+    ('!', "exclamation mark"),
+    ('"', "double quote"),
+    ('#', "hash"),
+    ('$', "dollar"),
+    ('%', "percent"),
+    ('&', "ampersand"),
+    ('\'', "apostrophe"),
+    ('(', "left parenthesis"),
+    (')', "right parenthesis"),
+    ('*', "asterisk"),
+    ('+', "plus"),
+    (',', "comma"),
+    ('-', "hyphen"),
+    ('.', "period"),
+    ('/', "slash"),
+    (':', "colon"),
+    (';', "semicolon"),
+    ('<', "less than"),
+    ('=', "equals"),
+    ('>', "greater than"),
+    ('?', "question mark"),
+    ('@', "at sign"),
+    ('[', "left bracket"),
+    ('\\', "backslash"),
+    (']', "right bracket"),
+    ('^', "caret"),
+    ('_', "underscore"),
+    ('`', "backtick"),
+    ('{', "left brace"),
+    ('|', "vertical bar"),
+    ('}', "right brace"),
+    ('~', "tilde"),
+];
+
+/*
+    Splits a password into groups of characters.
+
+    Arguments:
+    - `password`: A string slice representing the password.
+    - `size`: The number of characters of a group.
+
+    Returns:
+    A vector of the groups, the last one being shorter if the length is not a multiple of `size`.
+*/
+pub fn chunk_password(password: &str, size: usize) -> Vec<String> {
+    let characters: Vec<char> = password.chars().collect();
+    characters
+        .chunks(size.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/*
+    Tells the class of a character, to color it.
+
+    Arguments:
+    - `character`: The character to classify.
+
+    Returns:
+    The `CharacterClass` of the character, `Symbol` for anything but ASCII letters and digits.
+*/
+pub fn character_class(character: char) -> CharacterClass {
+    if LOWERCASE.contains(character) {
+        CharacterClass::Lowercase
+    } else if UPPERCASE.contains(character) {
+        CharacterClass::Uppercase
+    } else if DIGITS.contains(character) {
+        CharacterClass::Digit
+    } else {
+        CharacterClass::Symbol
+    }
+}
+
+/*
+    Names a character that looks like another one in many fonts.
+
+    Arguments:
+    - `character`: The character to name.
+
+    Returns:
+    An unambiguous name such as "small letter L", or `None` if the character is not ambiguous.
+*/
+pub fn ambiguous_name(character: char) -> Option<&'static str> {
+    AMBIGUOUS
+        .iter()
+        .find(|(ambiguous, _)| *ambiguous == character)
+        .map(|(_, name)| *name)
+}
+
+/*
+    Spells a character phonetically.
+
+    Arguments:
+    - `character`: The character to spell.
+
+    Returns:
+    A `String` such as "capital Alfa", "alfa", "Seven" or "at sign". Characters without a
+    name are returned as they are.
+*/
+pub fn spell_character(character: char) -> String {
+    let index = |alphabet: &str| alphabet.chars().position(|c| c == character);
+    if let Some(index) = index(LOWERCASE) {
+        NATO_ALPHABET[index].to_lowercase()
+    } else if let Some(index) = index(UPPERCASE) {
+        format!("capital {}", NATO_ALPHABET[index])
+    } else if let Some(index) = index(DIGITS) {
+        DIGIT_NAMES[index].to_string()
+    } else {
+        SYMBOL_NAMES
+            .iter()
+            .find(|(symbol, _)| *symbol == character)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| character.to_string())
+    }
+}
+
+/*
+    Spells a password phonetically.
+
+    Arguments:
+    - `password`: A string slice representing the password.
+
+    Returns:
+    A `String` of the spelled characters, separated by commas.
+*/
+pub fn spell_password(password: &str) -> String {
+    password
+        .chars()
+        .map(spell_character)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{LOOKALIKES, SYMBOLS};
+
+    #[test]
+    fn test_chunk_password() {
+        assert_eq!(chunk_password("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(chunk_password("abcd", 4), ["abcd"]);
+        assert!(chunk_password("", 4).is_empty());
+    }
+
+    #[test]
+    fn test_character_class() {
+        assert_eq!(character_class('a'), CharacterClass::Lowercase);
+        assert_eq!(character_class('Z'), CharacterClass::Uppercase);
+        assert_eq!(character_class('7'), CharacterClass::Digit);
+        assert_eq!(character_class('%'), CharacterClass::Symbol);
+    }
+
+    #[test]
+    fn test_ambiguous_name() {
+        assert_eq!(ambiguous_name('l'), Some("small letter L"));
+        assert_eq!(ambiguous_name('1'), Some("digit one"));
+        assert_eq!(ambiguous_name('O'), Some("capital letter O"));
+        assert_eq!(ambiguous_name('0'), Some("digit zero"));
+        assert_eq!(ambiguous_name('a'), None);
+        assert!(LOOKALIKES.chars().all(|c| ambiguous_name(c).is_some()));
+    }
+
+    #[test]
+    fn test_spell_password() {
+        assert_eq!(
+            spell_password("aZ7%x"),
+            "alfa, capital Zulu, Seven, percent, x-ray"
+        );
+    }
+
+    #[test]
+    fn test_every_symbol_has_a_name() {
+        for symbol in SYMBOLS.chars() {
+            assert_ne!(spell_character(symbol), symbol.to_string(), "{}", symbol);
+        }
+    }
+}
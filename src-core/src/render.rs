@@ -0,0 +1,217 @@
+/*
+    This module contains a LessPass compatible renderer for custom character sets.
+
+    The `character_classes` function builds the alphabet of every enabled character class,
+    removing the excluded characters, and the look-alike ones if asked, and replacing the
+    symbols with the allowed ones.
+    The `render_password` function renders the entropy over those alphabets exactly like
+    LessPass does: it picks characters from the union of the classes, then one character of
+    every class, and inserts the latter at pseudo-random positions. Every enabled class is
+    therefore represented at least once in the password.
+
+    The file also includes unit tests checking that the standard alphabets render the same
+    passwords as LessPass.
+*/
+
+use crate::passgen::PasswordOptions;
+
+pub const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+pub const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const DIGITS: &str = "0123456789";
+pub const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+// Characters that look alike in many fonts, left out when `no_lookalikes` is set
+pub const LOOKALIKES: &str = "0Oo1lI|'`";
+
+/*
+    Builds the alphabets of the enabled character classes.
+
+    Arguments:
+    - `options`: A reference to the `PasswordOptions` containing the enabled classes, the
+      excluded characters, whether to exclude look-alikes and the allowed symbols.
+
+    Returns:
+    A vector with the alphabet of every enabled class, in the LessPass order. A class whose
+    characters are all excluded is left out.
+*/
+pub fn character_classes(options: &PasswordOptions) -> Vec<Vec<char>> {
+    let symbols = options.allowed_symbols.as_deref().unwrap_or(SYMBOLS);
+    let mut excluded = options.excluded_characters.clone();
+    if options.no_lookalikes {
+        excluded.push_str(LOOKALIKES);
+    }
+    [
+        (options.lowercase, LOWERCASE),
+        (options.uppercase, UPPERCASE),
+        (options.digits, DIGITS),
+        (options.symbols, symbols),
+    ]
+    .into_iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, alphabet)| alphabet_without(alphabet, &excluded))
+    .filter(|alphabet| !alphabet.is_empty())
+    .collect()
+}
+
+/*
+    Renders a password from the entropy over custom character classes.
+
+    Arguments:
+    - `entropy`: A slice of bytes holding the big-endian entropy.
+    - `classes`: The alphabets of the character classes, as returned by `character_classes`.
+    - `length`: The length of the password.
+
+    Returns:
+    A string representing the rendered password.
+*/
+pub fn render_password(entropy: &[u8], classes: &[Vec<char>], length: usize) -> String {
+    let all_characters: Vec<char> = classes.concat();
+    if all_characters.is_empty() {
+        return String::new();
+    }
+
+    let mut quotient = entropy.to_vec();
+    let mut password: Vec<char> = Vec::new();
+    while password.len() < length.saturating_sub(classes.len()) {
+        let remainder = divmod(&mut quotient, all_characters.len());
+        password.push(all_characters[remainder]);
+    }
+
+    let one_per_class: Vec<char> = classes
+        .iter()
+        .map(|class| class[divmod(&mut quotient, class.len())])
+        .collect();
+    for character in one_per_class {
+        let position = divmod(&mut quotient, password.len().max(1));
+        password.insert(position.min(password.len()), character);
+    }
+
+    password.into_iter().collect()
+}
+
+// Deduplicates the alphabet while removing the excluded characters
+fn alphabet_without(alphabet: &str, excluded: &str) -> Vec<char> {
+    let mut characters: Vec<char> = Vec::new();
+    for character in alphabet.chars() {
+        if !excluded.contains(character) && !characters.contains(&character) {
+            characters.push(character);
+        }
+    }
+    characters
+}
+
+// Divides the big-endian number in place and returns the remainder
+pub(crate) fn divmod(quotient: &mut [u8], divisor: usize) -> usize {
+    let mut remainder = 0usize;
+    for byte in quotient.iter_mut() {
+        let current = (remainder << 8) | *byte as usize;
+        *byte = (current / divisor) as u8;
+        remainder = current % divisor;
+    }
+    remainder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lesspass::CharacterSet;
+
+    fn entropy(domain: &str, login: &str, master_password: &str, counter: u32) -> Vec<u8> {
+        let salt = lesspass::generate_salt(domain, login, counter);
+        lesspass::generate_entropy(master_password, &salt, lesspass::Algorithm::SHA256, 100000)
+    }
+
+    fn options(excluded_characters: &str, allowed_symbols: Option<&str>) -> PasswordOptions {
+        PasswordOptions {
+            excluded_characters: excluded_characters.to_string(),
+            allowed_symbols: allowed_symbols.map(str::to_string),
+            ..PasswordOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_standard_alphabets_match_lesspass() {
+        let entropy = entropy("lorem ipsum", "lorem ipsum", "lorem ipsum", 1);
+        let classes = character_classes(&options("", None));
+
+        for length in [5, 16, 35] {
+            assert_eq!(
+                render_password(&entropy, &classes, length),
+                lesspass::render_password(&entropy, CharacterSet::All, length)
+            );
+        }
+        assert_eq!(render_password(&entropy, &classes, 16), "fV1^3lS*'[knImg8");
+    }
+
+    #[test]
+    fn test_character_classes() {
+        let classes = character_classes(&PasswordOptions {
+            uppercase: false,
+            ..options("abc0\"'\\", Some("!@#!"))
+        });
+
+        assert_eq!(classes.len(), 3);
+        assert_eq!(classes[0].len(), 23);
+        assert_eq!(classes[1].len(), 9);
+        assert_eq!(classes[2], vec!['!', '@', '#']);
+    }
+
+    #[test]
+    fn test_character_classes_drops_exhausted_class() {
+        let classes = character_classes(&options(DIGITS, None));
+
+        assert_eq!(classes.len(), 3);
+        assert!(classes.iter().all(|class| !class.contains(&'0')));
+    }
+
+    #[test]
+    fn test_render_password_excluded_characters() {
+        let entropy = entropy("example.org", "contact@example.org", "password", 1);
+        let excluded = "\"'\\<>`";
+        let classes = character_classes(&options(excluded, None));
+
+        let password = render_password(&entropy, &classes, 16);
+
+        assert_eq!(password, "+Pk7I+-Jn|~9~E{|");
+        assert!(!password.chars().any(|c| excluded.contains(c)));
+    }
+
+    #[test]
+    fn test_character_classes_without_lookalikes() {
+        let classes = character_classes(&PasswordOptions {
+            no_lookalikes: true,
+            ..options("", None)
+        });
+
+        assert_eq!(classes[0].len(), 24);
+        assert_eq!(classes[1].len(), 24);
+        assert_eq!(classes[2].len(), 8);
+        assert_eq!(classes[3].len(), 29);
+        assert!(classes.concat().iter().all(|c| !LOOKALIKES.contains(*c)));
+    }
+
+    #[test]
+    fn test_render_password_allowed_symbols() {
+        let entropy = entropy("example.org", "contact@example.org", "password", 1);
+        let classes = character_classes(&options("", Some("-_")));
+
+        let password = render_password(&entropy, &classes, 16);
+
+        assert_eq!(password, "ES-XSsZQ_9bGf7D6");
+    }
+
+    #[test]
+    fn test_render_password_contains_every_class() {
+        let entropy = entropy("lorem ipsum", "lorem ipsum", "lorem ipsum", 1);
+        let classes = character_classes(&options("aeiou", Some("#")));
+
+        for length in 4..=35 {
+            let password: Vec<char> = render_password(&entropy, &classes, length)
+                .chars()
+                .collect();
+            assert_eq!(password.len(), length);
+            assert!(classes
+                .iter()
+                .all(|class| password.iter().any(|c| class.contains(c))));
+        }
+    }
+}
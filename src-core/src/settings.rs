@@ -0,0 +1,312 @@
+/*
+   This module defines a Settings struct for password generation, the parameters a profile
+   remembers for a site. The preferences of the device are kept in the `preferences` module.
+
+   The `Settings` struct holds the character sets, length and counter, the key derivation
+   parameters, the policy preset and the output mode with its options.
+   The `update_disabled_characters` function updates the list of disabled characters based on the provided settings.
+   The `active_policy` function looks up the policy the generated passwords must match.
+   The `password_options` function builds the generation options from the provided settings.
+*/
+
+use serde::{Deserialize, Serialize};
+
+use crate::mnemonic::DEFAULT_MNEMONIC_WORDS;
+use crate::passgen::{Algorithm, Mode, PasswordOptions, DEFAULT_ITERATIONS};
+use crate::passphrase::{DEFAULT_SEPARATOR, DEFAULT_WORDS};
+use crate::policy::{preset, Policy};
+use crate::totp::DEFAULT_DIGITS;
+
+// Output modes stored in `Settings::mode`
+pub const MODE_PASSWORD: u64 = 0;
+pub const MODE_PASSPHRASE: u64 = 1;
+pub const MODE_PIN: u64 = 2;
+pub const MODE_MNEMONIC: u64 = 3;
+
+// Define a struct to hold settings
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub size: u64,
+    pub counter: u64,
+    pub lowercase: u64,
+    pub uppercase: u64,
+    pub numbers: u64,
+    pub symbols: u64,
+    pub disabled: String,
+    pub version: u64,
+    pub algorithm: u64,
+    pub iterations: u64,
+    pub excluded_characters: String,
+    pub allowed_symbols: String,
+    pub no_lookalikes: u64,
+    pub policy: String,
+    pub sub_counter: u64,
+    pub mode: u64,
+    pub words: u64,
+    pub separator: String,
+    pub capitalize: u64,
+    pub mnemonic_words: u64,
+    pub totp_digits: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            size: 16,
+            counter: 1,
+            lowercase: 1,
+            uppercase: 1,
+            numbers: 1,
+            symbols: 1,
+            disabled: String::new(),
+            version: 2,
+            algorithm: Algorithm::default().digest_size(),
+            iterations: DEFAULT_ITERATIONS as u64,
+            excluded_characters: String::new(),
+            allowed_symbols: String::new(),
+            no_lookalikes: 0,
+            policy: String::new(),
+            sub_counter: 0,
+            mode: MODE_PASSWORD,
+            words: DEFAULT_WORDS as u64,
+            separator: DEFAULT_SEPARATOR.to_string(),
+            capitalize: 0,
+            mnemonic_words: DEFAULT_MNEMONIC_WORDS as u64,
+            totp_digits: DEFAULT_DIGITS as u64,
+        }
+    }
+}
+
+/*
+    Updates the list of disabled characters based on the provided settings.
+
+    Arguments:
+    - `settings`: A reference to the `Settings` struct containing the user's preferences.
+
+    Returns:
+    A `String` containing the updated list of disabled characters.
+*/
+pub fn update_disabled_characters(settings: &Settings) -> String {
+    if settings.lowercase == 1
+        && settings.uppercase == 0
+        && settings.numbers == 0
+        && settings.symbols == 0
+    {
+        "a-z".to_string()
+    } else if settings.lowercase == 0
+        && settings.uppercase == 1
+        && settings.numbers == 0
+        && settings.symbols == 0
+    {
+        "A-Z".to_string()
+    } else if settings.lowercase == 0
+        && settings.uppercase == 0
+        && settings.numbers == 1
+        && settings.symbols == 0
+    {
+        "0-9".to_string()
+    } else if settings.lowercase == 0
+        && settings.uppercase == 0
+        && settings.numbers == 0
+        && settings.symbols == 1
+    {
+        "%!@".to_string()
+    } else {
+        "".to_string()
+    }
+}
+
+/*
+    Looks up the policy the generated passwords must match.
+
+    Arguments:
+    - `settings`: A reference to the `Settings` struct containing the user's preferences.
+
+    Returns:
+    The preset `Policy` of the settings, or `None` if none is set or if the output mode is a
+    passphrase or a mnemonic, which policies do not apply to.
+*/
+pub fn active_policy(settings: &Settings) -> Option<&'static Policy> {
+    preset(&settings.policy).filter(|_| matches!(settings.mode, MODE_PASSWORD | MODE_PIN))
+}
+
+/*
+    Builds the password generation options from the input parameters.
+
+    Arguments:
+    - `website`: A string slice representing the website domain, already normalized if enabled.
+    - `username`: A string slice representing the username.
+    - `password`: A string slice representing the master password.
+    - `settings`: A reference to the `Settings` struct containing the user's preferences.
+
+    Returns:
+    A `PasswordOptions` struct ready to be passed to `generate_password`.
+*/
+pub fn password_options(
+    website: &str,
+    username: &str,
+    password: &str,
+    settings: &Settings,
+) -> PasswordOptions {
+    PasswordOptions {
+        domain: website.to_string(),
+        login: username.to_string(),
+        master_password: password.to_string(),
+        lowercase: settings.lowercase != 0,
+        uppercase: settings.uppercase != 0,
+        digits: settings.numbers != 0,
+        symbols: settings.symbols != 0,
+        length: settings.size as usize,
+        counter: settings.counter as u32,
+        version: settings.version as u32,
+        algorithm: Algorithm::from_digest_size(settings.algorithm),
+        iterations: settings.iterations as u32,
+        excluded_characters: settings.excluded_characters.clone(),
+        allowed_symbols: Some(settings.allowed_symbols.clone())
+            .filter(|symbols| !symbols.is_empty()),
+        no_lookalikes: settings.no_lookalikes != 0,
+        sub_counter: settings.sub_counter as u32,
+        mode: match settings.mode {
+            MODE_PASSPHRASE => Mode::Passphrase {
+                words: settings.words as usize,
+                separator: settings.separator.clone(),
+                capitalize: settings.capitalize != 0,
+            },
+            MODE_PIN => Mode::Pin,
+            MODE_MNEMONIC => Mode::Mnemonic {
+                words: settings.mnemonic_words as usize,
+            },
+            _ => Mode::Password,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passgen::generate_password;
+
+    #[test]
+    fn test_update_disabled_characters() {
+        let only = |lowercase, uppercase, numbers, symbols| Settings {
+            lowercase,
+            uppercase,
+            numbers,
+            symbols,
+            ..Settings::default()
+        };
+
+        assert_eq!(update_disabled_characters(&only(1, 0, 0, 0)), "a-z");
+        assert_eq!(update_disabled_characters(&only(0, 1, 0, 0)), "A-Z");
+        assert_eq!(update_disabled_characters(&only(0, 0, 1, 0)), "0-9");
+        assert_eq!(update_disabled_characters(&only(0, 0, 0, 1)), "%!@");
+        assert_eq!(update_disabled_characters(&only(1, 1, 0, 0)), "");
+    }
+
+    #[test]
+    fn test_password_options_default_settings() {
+        let options = password_options(
+            "lorem ipsum",
+            "lorem ipsum",
+            "lorem ipsum",
+            &Settings::default(),
+        );
+
+        assert_eq!(generate_password(options), "fV1^3lS*'[knImg8");
+    }
+
+    #[test]
+    fn test_password_options_kdf_settings() {
+        let settings = Settings {
+            algorithm: 512,
+            iterations: 100000,
+            ..Settings::default()
+        };
+
+        let options = password_options("lorem ipsum", "lorem ipsum", "lorem ipsum", &settings);
+
+        assert_eq!(options.algorithm, Algorithm::Sha512);
+        assert_eq!(generate_password(options), "^aqiM!GwRqy9r7&~");
+    }
+
+    #[test]
+    fn test_password_options_custom_characters() {
+        let settings = Settings {
+            excluded_characters: "<>".to_string(),
+            ..Settings::default()
+        };
+
+        let options = password_options("site", "login", "master", &settings);
+
+        assert_eq!(options.excluded_characters, "<>");
+        assert_eq!(options.allowed_symbols, None);
+    }
+
+    #[test]
+    fn test_password_options_passphrase() {
+        let settings = Settings {
+            mode: MODE_PASSPHRASE,
+            capitalize: 1,
+            ..Settings::default()
+        };
+
+        let options = password_options("lorem ipsum", "lorem ipsum", "lorem ipsum", &settings);
+
+        assert_eq!(
+            generate_password(options),
+            "Carport-Sanding-Crazed-Ahoy-Liability-Discolor"
+        );
+    }
+
+    #[test]
+    fn test_password_options_pin() {
+        let settings = Settings {
+            mode: MODE_PIN,
+            size: 4,
+            ..Settings::default()
+        };
+
+        let options = password_options("lorem ipsum", "lorem ipsum", "lorem ipsum", &settings);
+
+        assert_eq!(options.mode, Mode::Pin);
+        assert_eq!(generate_password(options), "0796");
+    }
+
+    #[test]
+    fn test_active_policy() {
+        let settings = Settings {
+            policy: "bank".to_string(),
+            ..Settings::default()
+        };
+        let passphrase = Settings {
+            mode: MODE_PASSPHRASE,
+            ..settings.clone()
+        };
+
+        assert_eq!(active_policy(&settings), preset("bank"));
+        assert_eq!(active_policy(&passphrase), None);
+        assert_eq!(active_policy(&Settings::default()), None);
+    }
+
+    #[test]
+    fn test_password_options_mnemonic() {
+        let settings = Settings {
+            mode: MODE_MNEMONIC,
+            mnemonic_words: 24,
+            ..Settings::default()
+        };
+
+        let options = password_options("lorem ipsum", "lorem ipsum", "lorem ipsum", &settings);
+
+        assert_eq!(options.mode, Mode::Mnemonic { words: 24 });
+    }
+
+    #[test]
+    fn test_settings_without_kdf_fields() {
+        let settings: Settings = serde_json::from_str(r#"{"size": 20, "counter": 2}"#).unwrap();
+
+        assert_eq!(settings.algorithm, 256);
+        assert_eq!(settings.iterations, 100000);
+    }
+}
@@ -0,0 +1,582 @@
+/*
+    This module contains a strength estimator for master passwords, in the spirit of zxcvbn.
+
+    The `estimate` function looks for the patterns attackers try first: common passwords and
+    dictionary words (also reversed or with l33t substitutions), dates and years, keyboard walks,
+    repeats and sequences. It then finds the cheapest way to cover the password with these
+    patterns, brute-forcing the characters none of them matches. The resulting number of guesses
+    gives a score from 0 (too guessable) to 4 (very unguessable) and a crack time estimate.
+    The `format_crack_time` function renders that estimate for humans.
+
+    The file also includes unit tests for every kind of pattern.
+*/
+
+use crate::wordlists::eff_large_words;
+
+// Guesses per second of an offline attack on a slow hash such as PBKDF2
+pub const GUESSES_PER_SECOND: f64 = 1e4;
+
+// Passwords longer than this are only analyzed up to it, which is already unguessable
+const MAX_ANALYZED_LENGTH: usize = 64;
+const MAX_WORD_LENGTH: usize = 16;
+
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_SINGLE_GUESSES: f64 = 10.0;
+const MIN_GUESSES: f64 = 50.0;
+const DATE_GUESSES: f64 = 365.0 * 100.0;
+const YEAR_GUESSES: f64 = 100.0;
+const KEYBOARD_STARTING_POSITIONS: f64 = 47.0;
+const KEYBOARD_AVERAGE_DEGREE: f64 = 4.0;
+
+// Upper bounds of the guesses of the scores 0 to 3
+const SCORE_THRESHOLDS: [f64; 4] = [1e3, 1e6, 1e8, 1e10];
+
+// Most common passwords, ordered by frequency in leaked password datasets
+const COMMON_PASSWORDS: [&str; 48] = [
+    "123456",
+    "password",
+    "12345678",
+    "qwerty",
+    "123456789",
+    "12345",
+    "1234",
+    "111111",
+    "1234567",
+    "dragon",
+    "123123",
+    "baseball",
+    "abc123",
+    "football",
+    "monkey",
+    "letmein",
+    "696969",
+    "shadow",
+    "master",
+    "666666",
+    "qwertyuiop",
+    "123321",
+    "mustang",
+    "1234567890",
+    "michael",
+    "654321",
+    "superman",
+    "1qaz2wsx",
+    "7777777",
+    "121212",
+    "000000",
+    "qazwsx",
+    "123qwe",
+    "killer",
+    "trustno1",
+    "jordan",
+    "jennifer",
+    "zxcvbnm",
+    "asdfgh",
+    "hunter",
+    "sunshine",
+    "iloveyou",
+    "princess",
+    "welcome",
+    "admin",
+    "login",
+    "passw0rd",
+    "starwars",
+];
+
+// Rows of a QWERTY keyboard, unshifted then shifted
+const KEYBOARD_ROWS: [(&str, &str); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+
+const L33T_SUBSTITUTIONS: [(char, char); 9] = [
+    ('4', 'a'),
+    ('@', 'a'),
+    ('3', 'e'),
+    ('1', 'i'),
+    ('!', 'i'),
+    ('0', 'o'),
+    ('$', 's'),
+    ('5', 's'),
+    ('7', 't'),
+];
+
+// Define an enum of the patterns a password can be made of
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pattern {
+    CommonPassword,
+    Dictionary,
+    Date,
+    Keyboard,
+    Repeat,
+    Sequence,
+    BruteForce,
+}
+
+// Define a struct to hold the estimated strength of a password
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strength {
+    pub guesses: f64,
+    pub score: u8,
+    pub crack_time_seconds: f64,
+    pub patterns: Vec<Pattern>,
+}
+
+// Define a struct to hold a pattern found in the password, from `start` to `end` (exclusive)
+#[derive(Clone, Debug)]
+struct Match {
+    start: usize,
+    end: usize,
+    guesses: f64,
+    pattern: Pattern,
+}
+
+impl Strength {
+    /*
+        Explains why the password is guessable.
+
+        Returns:
+        A warning about the main pattern of the password, or `None` if its score is above 2.
+    */
+    pub fn warning(&self) -> Option<&'static str> {
+        if self.score > 2 {
+            return None;
+        }
+        let pattern = self
+            .patterns
+            .iter()
+            .find(|pattern| **pattern != Pattern::BruteForce);
+        Some(match pattern {
+            Some(Pattern::CommonPassword) => "This is a very common password",
+            Some(Pattern::Dictionary) => "Dictionary words are easy to guess",
+            Some(Pattern::Date) => "Dates and years are easy to guess",
+            Some(Pattern::Keyboard) => "Keyboard patterns are easy to guess",
+            Some(Pattern::Repeat) => "Repeated characters are easy to guess",
+            Some(Pattern::Sequence) => "Sequences like abc or 6543 are easy to guess",
+            _ => "Add more characters or words",
+        })
+    }
+}
+
+/*
+    Estimates the strength of a password.
+
+    Arguments:
+    - `password`: A string slice representing the password.
+
+    Returns:
+    A `Strength` struct holding the number of guesses, the score from 0 to 4, the crack time
+    in seconds and the patterns found in the password, in order.
+*/
+pub fn estimate(password: &str) -> Strength {
+    let chars: Vec<char> = password.chars().take(MAX_ANALYZED_LENGTH).collect();
+    let (guesses, patterns) = minimum_guesses(&chars, true);
+
+    Strength {
+        guesses,
+        score: SCORE_THRESHOLDS
+            .iter()
+            .take_while(|&&threshold| guesses >= threshold)
+            .count() as u8,
+        crack_time_seconds: guesses / GUESSES_PER_SECOND,
+        patterns,
+    }
+}
+
+/*
+    Formats a crack time for humans.
+
+    Arguments:
+    - `seconds`: The crack time in seconds.
+
+    Returns:
+    A string such as "less than a second", "3 hours" or "centuries".
+*/
+pub fn format_crack_time(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("second", 1.0),
+        ("minute", 60.0),
+        ("hour", 3600.0),
+        ("day", 86400.0),
+        ("month", 86400.0 * 31.0),
+        ("year", 86400.0 * 365.0),
+    ];
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    if seconds >= 100.0 * 86400.0 * 365.0 {
+        return "centuries".to_string();
+    }
+    let (unit, size) = UNITS
+        .iter()
+        .rev()
+        .find(|(_, size)| seconds >= *size)
+        .expect("a unit of at most one second");
+    let count = (seconds / size).round() as u64;
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+// Finds the cheapest sequence of patterns covering the characters
+fn minimum_guesses(chars: &[char], with_repeats: bool) -> (f64, Vec<Pattern>) {
+    let mut matches = dictionary_matches(chars);
+    matches.extend(date_matches(chars));
+    matches.extend(keyboard_matches(chars));
+    matches.extend(sequence_matches(chars));
+    if with_repeats {
+        matches.extend(repeat_matches(chars));
+    }
+
+    // best[end] holds the guesses of the cheapest cover of chars[..end] and its last match
+    let mut best: Vec<(f64, Option<Match>)> = vec![(1.0, None); chars.len() + 1];
+    for end in 1..=chars.len() {
+        best[end] = (
+            best[end - 1].0 * BRUTEFORCE_CARDINALITY,
+            Some(Match {
+                start: end - 1,
+                end,
+                guesses: BRUTEFORCE_CARDINALITY,
+                pattern: Pattern::BruteForce,
+            }),
+        );
+        for candidate in matches.iter().filter(|candidate| candidate.end == end) {
+            let minimum = if candidate.end - candidate.start == 1 {
+                MIN_SINGLE_GUESSES
+            } else {
+                MIN_GUESSES
+            };
+            let guesses = best[candidate.start].0 * candidate.guesses.max(minimum);
+            if guesses < best[end].0 {
+                best[end] = (guesses, Some(candidate.clone()));
+            }
+        }
+    }
+
+    let mut patterns = Vec::new();
+    let mut end = chars.len();
+    while let Some(last) = &best[end].1 {
+        if last.pattern != Pattern::BruteForce || patterns.last() != Some(&Pattern::BruteForce) {
+            patterns.push(last.pattern);
+        }
+        end = last.start;
+    }
+    patterns.reverse();
+    (best[chars.len()].0, patterns)
+}
+
+// Finds the common passwords and dictionary words, also reversed or with l33t substitutions
+fn dictionary_matches(chars: &[char]) -> Vec<Match> {
+    let wordlist = eff_large_words();
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for end in start + 1..=chars.len().min(start + MAX_WORD_LENGTH) {
+            let token = &chars[start..end];
+            let lowercase: String = token.iter().flat_map(|c| c.to_lowercase()).collect();
+            let uppercase = token.iter().filter(|c| c.is_uppercase()).count();
+            let case_variations = if uppercase == 0 {
+                1.0
+            } else if uppercase == token.len() || (uppercase == 1 && token[0].is_uppercase()) {
+                2.0
+            } else {
+                2f64.powi(uppercase as i32)
+            };
+
+            let reversed: String = lowercase.chars().rev().collect();
+            let unl33t: String = lowercase.chars().map(unl33t).collect();
+            let substitutions = lowercase
+                .chars()
+                .zip(unl33t.chars())
+                .filter(|(a, b)| a != b);
+            let variants = [
+                (lowercase.clone(), 1.0),
+                (reversed, 2.0),
+                (unl33t.clone(), 2f64.powi(substitutions.count() as i32)),
+            ];
+            for (word, variation) in variants {
+                if let Some((guesses, pattern)) = dictionary_rank(&word, &wordlist) {
+                    matches.push(Match {
+                        start,
+                        end,
+                        guesses: guesses * case_variations * variation,
+                        pattern,
+                    });
+                    break;
+                }
+            }
+        }
+    }
+    matches
+}
+
+// Returns the guesses needed to find a word in the dictionaries
+fn dictionary_rank(word: &str, wordlist: &[&str]) -> Option<(f64, Pattern)> {
+    if let Some(rank) = COMMON_PASSWORDS.iter().position(|common| *common == word) {
+        return Some(((rank + 1) as f64, Pattern::CommonPassword));
+    }
+    if word.len() >= 3 && wordlist.binary_search(&word).is_ok() {
+        return Some((wordlist.len() as f64, Pattern::Dictionary));
+    }
+    None
+}
+
+fn unl33t(c: char) -> char {
+    L33T_SUBSTITUTIONS
+        .iter()
+        .find(|(l33t, _)| *l33t == c)
+        .map_or(c, |(_, letter)| *letter)
+}
+
+// Finds the years and the dates with or without separators, such as 1990, 120590 or 12/05/1990
+fn date_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for end in start + 4..=chars.len().min(start + 10) {
+            let token: String = chars[start..end].iter().collect();
+            let guesses = if token.len() == 4 && parse_year(&token).is_some() {
+                YEAR_GUESSES
+            } else if is_date(&token) {
+                DATE_GUESSES
+            } else {
+                continue;
+            };
+            matches.push(Match {
+                start,
+                end,
+                guesses,
+                pattern: Pattern::Date,
+            });
+        }
+    }
+    matches
+}
+
+fn is_date(token: &str) -> bool {
+    let parts: Vec<&str> = match token.chars().find(|c| "/-._ ".contains(*c)) {
+        Some(separator) => token.split(separator).collect(),
+        None if !token.chars().all(|c| c.is_ascii_digit()) => return false,
+        None if token.len() == 6 => vec![&token[..2], &token[2..4], &token[4..]],
+        None if token.len() == 8 => {
+            return is_date_of(&token[..2], &token[2..4], &token[4..])
+                || is_date_of(&token[2..4], &token[..2], &token[4..])
+                || is_date_of(&token[6..], &token[4..6], &token[..4]);
+        }
+        None => return false,
+    };
+    parts.len() == 3
+        && (is_date_of(parts[0], parts[1], parts[2])
+            || is_date_of(parts[1], parts[0], parts[2])
+            || is_date_of(parts[2], parts[1], parts[0]))
+}
+
+fn is_date_of(day: &str, month: &str, year: &str) -> bool {
+    let number = |part: &str, max: u32| {
+        (1..=2).contains(&part.len())
+            && part.chars().all(|c| c.is_ascii_digit())
+            && part
+                .parse()
+                .is_ok_and(|value: u32| (1..=max).contains(&value))
+    };
+    let year = (year.len() == 2 && year.chars().all(|c| c.is_ascii_digit()))
+        || (year.len() == 4 && parse_year(year).is_some());
+    number(day, 31) && number(month, 12) && year
+}
+
+fn parse_year(token: &str) -> Option<u32> {
+    token
+        .parse()
+        .ok()
+        .filter(|year| (1900..=2099).contains(year) && token.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Finds the walks of three or more adjacent keys, such as qwer or zxcvfr
+fn keyboard_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = start + 1;
+        let mut turns = 0;
+        let mut direction = None;
+        while end < chars.len() {
+            let Some(step) = key_step(chars[end - 1], chars[end]) else {
+                break;
+            };
+            if direction != Some(step) {
+                turns += 1;
+                direction = Some(step);
+            }
+            end += 1;
+        }
+        if end - start >= 3 {
+            let shifted = chars[start..end]
+                .iter()
+                .any(|&c| key_position(c).is_some_and(|(_, _, shifted)| shifted));
+            matches.push(Match {
+                start,
+                end,
+                guesses: KEYBOARD_STARTING_POSITIONS
+                    * (end - start) as f64
+                    * KEYBOARD_AVERAGE_DEGREE.powi(turns - 1)
+                    * if shifted { 2.0 } else { 1.0 },
+                pattern: Pattern::Keyboard,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+// Returns the direction from a key to an adjacent one
+fn key_step(from: char, to: char) -> Option<(i32, i32)> {
+    let (from_row, from_column, _) = key_position(from)?;
+    let (to_row, to_column, _) = key_position(to)?;
+    let step = (to_row - from_row, to_column - from_column);
+    (step != (0, 0) && step.0.abs() <= 1 && step.1.abs() <= 1).then_some(step)
+}
+
+fn key_position(c: char) -> Option<(i32, i32, bool)> {
+    KEYBOARD_ROWS
+        .iter()
+        .enumerate()
+        .find_map(|(row, (unshifted, shifted))| {
+            if let Some(column) = unshifted.chars().position(|key| key == c) {
+                Some((row as i32, column as i32, false))
+            } else {
+                let column = shifted.chars().position(|key| key == c)?;
+                Some((row as i32, column as i32, true))
+            }
+        })
+}
+
+// Finds the shortest blocks repeated at least twice, such as aaa or abcabc
+fn repeat_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        let repeat = (1..=(chars.len() - start) / 2).find_map(|size| {
+            let block = &chars[start..start + size];
+            let count = chars[start..]
+                .chunks(size)
+                .take_while(|chunk| *chunk == block)
+                .count();
+            (count >= 2).then_some((block, count))
+        });
+        if let Some((block, count)) = repeat {
+            matches.push(Match {
+                start,
+                end: start + block.len() * count,
+                guesses: minimum_guesses(block, false).0 * count as f64,
+                pattern: Pattern::Repeat,
+            });
+        }
+    }
+    matches
+}
+
+// Finds the runs of three or more consecutive letters or digits, such as abcd or 6543
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + 1 < chars.len() {
+        let delta = chars[start + 1] as i32 - chars[start] as i32;
+        let mut end = start + 1;
+        while end < chars.len()
+            && (delta == 1 || delta == -1)
+            && chars[end] as i32 - chars[end - 1] as i32 == delta
+            && same_class(chars[end - 1], chars[end])
+        {
+            end += 1;
+        }
+        if end - start >= 3 {
+            let base = if "aAzZ019".contains(chars[start]) {
+                4.0
+            } else if chars[start].is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            matches.push(Match {
+                start,
+                end,
+                guesses: base * (end - start) as f64 * if delta < 0 { 2.0 } else { 1.0 },
+                pattern: Pattern::Sequence,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+fn same_class(a: char, b: char) -> bool {
+    (a.is_ascii_lowercase() && b.is_ascii_lowercase())
+        || (a.is_ascii_uppercase() && b.is_ascii_uppercase())
+        || (a.is_ascii_digit() && b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_very_weak_passwords() {
+        for password in ["", "a", "password", "P@ssw0rd", "drowssap", "123456"] {
+            assert_eq!(estimate(password).score, 0, "{} should score 0", password);
+        }
+        assert_eq!(
+            estimate("password").warning(),
+            Some("This is a very common password")
+        );
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(estimate("dolphin").patterns, vec![Pattern::Dictionary]);
+        assert_eq!(estimate("12/05/1990").patterns, vec![Pattern::Date]);
+        assert_eq!(estimate("19900512").patterns, vec![Pattern::Date]);
+        assert_eq!(estimate("qwertyu").patterns, vec![Pattern::Keyboard]);
+        assert_eq!(estimate("zxcvfr").patterns, vec![Pattern::Keyboard]);
+        assert_eq!(estimate("xkxkxkxk").patterns, vec![Pattern::Repeat]);
+        assert_eq!(estimate("lmnopq").patterns, vec![Pattern::Sequence]);
+        assert_eq!(
+            estimate("dolphin1990").patterns,
+            vec![Pattern::Dictionary, Pattern::Date]
+        );
+    }
+
+    #[test]
+    fn test_long_repeats() {
+        assert_eq!(estimate(&"a".repeat(100)).score, 0);
+    }
+
+    #[test]
+    fn test_strong_passwords() {
+        let passphrase = estimate("correct horse battery staple");
+        assert_eq!(passphrase.score, 4);
+        assert_eq!(passphrase.warning(), None);
+
+        assert_eq!(estimate("fV1^3lS*'[knImg8").score, 4);
+    }
+
+    #[test]
+    fn test_score_increases_with_length() {
+        let scores: Vec<u8> = ["dolphin", "dolphin7T", "dolphin7T#q", "dolphin7T#qW2y"]
+            .iter()
+            .map(|password| estimate(password).score)
+            .collect();
+
+        assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(scores[0] < scores[3]);
+    }
+
+    #[test]
+    fn test_format_crack_time() {
+        assert_eq!(format_crack_time(0.5), "less than a second");
+        assert_eq!(format_crack_time(1.0), "1 second");
+        assert_eq!(format_crack_time(150.0), "3 minutes");
+        assert_eq!(format_crack_time(86400.0 * 400.0), "1 year");
+        assert_eq!(format_crack_time(1e12), "centuries");
+    }
+}
@@ -0,0 +1,249 @@
+/*
+    This module contains an RFC 6238 TOTP authenticator deriving its seeds from the
+    master password, so that 2FA enrollments can be rebuilt after losing a phone.
+
+    The `derive_seed` function derives a 160-bit seed per site and login with the PBKDF2
+    parameters of the options and a salt prefixed with a distinct context, so the seed
+    never equals the entropy of a password.
+    The `totp` function computes the code of a seed at a given time, `seconds_remaining`
+    the time left before the next code, and `otpauth_uri` the URI used to enroll the seed
+    in an authenticator app.
+
+    The file also includes unit tests using the reference values of RFC 6238.
+*/
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+use crate::passgen::PasswordOptions;
+
+pub const PERIOD: u64 = 30;
+pub const DEFAULT_DIGITS: u32 = 6;
+
+// Number of digits of a code allowed by RFC 6238
+pub const MIN_DIGITS: u32 = 6;
+pub const MAX_DIGITS: u32 = 8;
+
+// Length of the seed, matching the output of HMAC-SHA1 as advised by RFC 4226
+const SEED_LENGTH: usize = 20;
+
+// Context prepended to the LessPass salt when deriving a seed
+const SEED_CONTEXT: &[u8] = b"rustedlesspass-totp:";
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/*
+    Derives the TOTP seed of a site and login.
+
+    Arguments:
+    - `options`: A reference to the `PasswordOptions` holding the site, login, counter,
+      master password and key derivation parameters.
+
+    Returns:
+    The 20 bytes of the seed.
+*/
+pub fn derive_seed(options: &PasswordOptions) -> Vec<u8> {
+    let mut salt = SEED_CONTEXT.to_vec();
+    salt.extend(lesspass::generate_salt(
+        &options.domain,
+        &options.login,
+        options.counter,
+    ));
+    let entropy = lesspass::generate_entropy(
+        &options.master_password,
+        &salt,
+        options.algorithm.to_lesspass(),
+        options.iterations,
+    );
+    entropy[..SEED_LENGTH].to_vec()
+}
+
+/*
+    Computes the TOTP code of a seed.
+
+    Arguments:
+    - `seed`: A slice of bytes holding the seed.
+    - `unix_time`: The current time, in seconds since the Unix epoch.
+    - `digits`: The number of digits of the code, clamped between `MIN_DIGITS` and `MAX_DIGITS`.
+
+    Returns:
+    A string containing the zero-padded code.
+*/
+pub fn totp(seed: &[u8], unix_time: u64, digits: u32) -> String {
+    hotp(
+        seed,
+        unix_time / PERIOD,
+        digits.clamp(MIN_DIGITS, MAX_DIGITS),
+    )
+}
+
+/*
+    Returns the number of seconds before the next TOTP code.
+
+    Arguments:
+    - `unix_time`: The current time, in seconds since the Unix epoch.
+
+    Returns:
+    The remaining seconds, between 1 and `PERIOD`.
+*/
+pub fn seconds_remaining(unix_time: u64) -> u64 {
+    PERIOD - unix_time % PERIOD
+}
+
+/*
+    Builds the URI used to enroll a seed in an authenticator app.
+
+    Arguments:
+    - `seed`: A slice of bytes holding the seed.
+    - `issuer`: A string slice representing the site, shown by the authenticator app.
+    - `login`: A string slice representing the login of the account.
+    - `digits`: The number of digits of the codes, clamped between `MIN_DIGITS` and `MAX_DIGITS`.
+
+    Returns:
+    An `otpauth://totp/` URI.
+*/
+pub fn otpauth_uri(seed: &[u8], issuer: &str, login: &str, digits: u32) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(login),
+        base32_encode(seed),
+        percent_encode(issuer),
+        digits.clamp(MIN_DIGITS, MAX_DIGITS),
+        PERIOD
+    )
+}
+
+/*
+    Encodes bytes in unpadded base32, as expected by authenticator apps.
+
+    Arguments:
+    - `bytes`: A slice of bytes to encode.
+
+    Returns:
+    A string of uppercase base32 characters.
+*/
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+// Computes an RFC 4226 HOTP code with HMAC-SHA1 and dynamic truncation
+fn hotp(seed: &[u8], counter: u64, digits: u32) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(seed).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    let code = binary as u64 % 10u64.pow(digits);
+    format!("{:0width$}", code, width = digits as usize)
+}
+
+// Percent-encodes every character but the unreserved ones of RFC 3986
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Seed of the SHA-1 reference values of RFC 6238, appendix B
+    const RFC_SEED: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_totp_rfc_6238_vectors() {
+        assert_eq!(totp(RFC_SEED, 59, 8), "94287082");
+        assert_eq!(totp(RFC_SEED, 1111111109, 8), "07081804");
+        assert_eq!(totp(RFC_SEED, 1111111111, 8), "14050471");
+        assert_eq!(totp(RFC_SEED, 1234567890, 8), "89005924");
+        assert_eq!(totp(RFC_SEED, 2000000000, 8), "69279037");
+        assert_eq!(totp(RFC_SEED, 20000000000, 8), "65353130");
+    }
+
+    #[test]
+    fn test_totp_six_digits() {
+        assert_eq!(totp(RFC_SEED, 59, 6), "287082");
+        assert_eq!(totp(RFC_SEED, 1111111109, 6), "081804");
+    }
+
+    #[test]
+    fn test_totp_clamps_digits() {
+        assert_eq!(totp(RFC_SEED, 59, 0), "287082");
+        assert_eq!(totp(RFC_SEED, 59, 20), "94287082");
+        assert!(otpauth_uri(RFC_SEED, "example.org", "", 20).contains("&digits=8&"));
+    }
+
+    #[test]
+    fn test_seconds_remaining() {
+        assert_eq!(seconds_remaining(59), 1);
+        assert_eq!(seconds_remaining(60), 30);
+    }
+
+    #[test]
+    fn test_base32_encode() {
+        // Test vectors of RFC 4648, without padding
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"fooba"), "MZXW6YTB");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_encode(RFC_SEED), "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        assert_eq!(
+            otpauth_uri(RFC_SEED, "example.org", "contact@example.org", 6),
+            "otpauth://totp/example.org:contact%40example.org?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=example.org&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_derive_seed() {
+        let options = PasswordOptions {
+            domain: "example.org".to_string(),
+            login: "contact@example.org".to_string(),
+            master_password: "password".to_string(),
+            ..PasswordOptions::default()
+        };
+
+        let seed = derive_seed(&options);
+
+        assert_eq!(seed.len(), SEED_LENGTH);
+        assert_eq!(base32_encode(&seed), "X2TIDO6SVSH66NZ7IDY22BSQRAH3U4X6");
+        assert_ne!(
+            seed,
+            derive_seed(&PasswordOptions {
+                counter: 2,
+                ..options
+            })
+        );
+    }
+}
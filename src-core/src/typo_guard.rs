@@ -0,0 +1,150 @@
+/*
+    This module defines a TypoGuard that remembers the fingerprint of the master password
+    used on a device, so that a mistyped master password is noticed before its wrong
+    passwords are used.
+
+    Only a salted SHA-256 hash of the three fingerprint icons is kept and the master password
+    is never stored. The hash does not hide the icons though: there are only 46^3, about 97k
+    combinations, and the salt is stored next to the hash, so anyone reading the storage of
+    the device recovers them instantly. The icons then rule out about 16 bits' worth of
+    master password guesses with one fast HMAC-SHA256 each, skipping the PBKDF2 derivation a
+    guess would otherwise cost. Remembering the fingerprint is thus left to the user, for
+    devices whose storage they trust.
+
+    The `remember` method stores the hash of a fingerprint, the `check` method compares
+    another fingerprint with it and `forget` clears it.
+
+    The file also includes unit tests for the match and mismatch cases.
+*/
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::import_export::{from_hex, to_hex};
+
+const SALT_LENGTH: usize = 16;
+
+// Define a struct to hold the salted hash of the remembered fingerprint
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TypoGuard {
+    pub enabled: bool,
+    pub salt: String,
+    pub hash: String,
+}
+
+// Define an enum of the results of a fingerprint check
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FingerprintCheck {
+    Unknown,
+    Match,
+    Mismatch,
+}
+
+impl TypoGuard {
+    /*
+        Returns whether a fingerprint is remembered.
+    */
+    pub fn is_remembered(&self) -> bool {
+        !self.hash.is_empty()
+    }
+
+    /*
+        Remembers a fingerprint with a new random salt.
+
+        Arguments:
+        - `fingerprint`: A slice of the icons returned by `fingerprint_calculate`.
+    */
+    pub fn remember(&mut self, fingerprint: &[String]) {
+        let mut salt = [0u8; SALT_LENGTH];
+        getrandom::getrandom(&mut salt).expect("random salt");
+        self.salt = to_hex(&salt);
+        self.hash = to_hex(&hash(&salt, fingerprint));
+    }
+
+    pub fn forget(&mut self) {
+        self.salt.clear();
+        self.hash.clear();
+    }
+
+    /*
+        Compares a fingerprint with the remembered one.
+
+        Arguments:
+        - `fingerprint`: A slice of the icons returned by `fingerprint_calculate`.
+
+        Returns:
+        `FingerprintCheck::Unknown` if no fingerprint is remembered, otherwise whether
+        the fingerprints match.
+    */
+    pub fn check(&self, fingerprint: &[String]) -> FingerprintCheck {
+        let Some(salt) = from_hex(&self.salt).filter(|_| self.is_remembered()) else {
+            return FingerprintCheck::Unknown;
+        };
+        if to_hex(&hash(&salt, fingerprint)) == self.hash {
+            FingerprintCheck::Match
+        } else {
+            FingerprintCheck::Mismatch
+        }
+    }
+}
+
+fn hash(salt: &[u8], fingerprint: &[String]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(fingerprint.join("\n").as_bytes());
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprintgen::fingerprint_calculate;
+
+    #[test]
+    fn test_check_remembered_fingerprint() {
+        let mut guard = TypoGuard::default();
+        assert_eq!(
+            guard.check(&fingerprint_calculate("lorem ipsum")),
+            FingerprintCheck::Unknown
+        );
+
+        guard.remember(&fingerprint_calculate("lorem ipsum"));
+
+        assert_eq!(
+            guard.check(&fingerprint_calculate("lorem ipsum")),
+            FingerprintCheck::Match
+        );
+        assert_eq!(
+            guard.check(&fingerprint_calculate("lorem ipsun")),
+            FingerprintCheck::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_remember_salts_the_hash() {
+        let fingerprint = fingerprint_calculate("lorem ipsum");
+        let mut first = TypoGuard::default();
+        let mut second = TypoGuard::default();
+
+        first.remember(&fingerprint);
+        second.remember(&fingerprint);
+
+        assert_ne!(first.hash, second.hash);
+        assert!(!first.hash.contains("lorem"));
+    }
+
+    #[test]
+    fn test_forget() {
+        let mut guard = TypoGuard::default();
+        guard.remember(&fingerprint_calculate("lorem ipsum"));
+
+        guard.forget();
+
+        assert!(!guard.is_remembered());
+        assert_eq!(
+            guard.check(&fingerprint_calculate("lorem ipsum")),
+            FingerprintCheck::Unknown
+        );
+    }
+}
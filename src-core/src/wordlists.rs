@@ -0,0 +1,38 @@
+/*
+    This module embeds the EFF large wordlist (https://www.eff.org/dice), which the
+    passphrases pick their words from and the strength estimator uses as a dictionary.
+
+    The list holds 7776 words in alphabetical order, each after its five dice rolls, as
+    published by the Electronic Frontier Foundation under the CC BY 3.0 US license.
+*/
+
+const EFF_LARGE_WORDLIST: &str = include_str!("wordlists/eff_large_wordlist.txt");
+
+/*
+    Lists the words of the EFF large wordlist.
+
+    Returns:
+    A vector of the 7776 words, without their dice rolls, in alphabetical order.
+*/
+pub(crate) fn eff_large_words() -> Vec<&'static str> {
+    EFF_LARGE_WORDLIST
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eff_large_words() {
+        let words = eff_large_words();
+
+        assert_eq!(words.len(), 7776);
+        assert_eq!(words[0], "abacus");
+        assert_eq!(words[7775], "zoom");
+        // The strength estimator looks words up by binary search
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
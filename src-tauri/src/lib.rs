@@ -1,14 +1,39 @@
+use rustedlesspass_core::settings::{password_options, Settings};
+use rustedlesspass_core::sshkey::{derive_ssh_key, SshKeyPair};
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
+fn ssh_key(host: &str, username: &str, password: &str, settings: Settings) -> SshKeyPair {
+    derive_ssh_key(&password_options(host, username, password, &settings))
+}
+
+#[tauri::command]
+fn copy_to_clipboard(app: AppHandle, text: String, timeout: u64) -> Result<(), String> {
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| e.to_string())?;
+    if timeout > 0 {
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(timeout));
+            // Anything copied since is kept
+            if app.clipboard().read_text().ok().as_deref() == Some(text.as_str()) {
+                let _ = app.clipboard().write_text(String::new());
+            }
+        });
+    }
+    Ok(())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .invoke_handler(tauri::generate_handler![ssh_key, copy_to_clipboard])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -11,45 +11,198 @@
     It manages the application state, handles user interactions, and renders the user interface.
 */
 
-use crate::fingerprintgen::fingerprint_calculate;
-use crate::password_utils::{update_disabled_characters, update_show_state};
-use crate::settings::Settings;
+use crate::clipboard::{copy_to_clipboard, is_tauri, ClearRetry, ClipboardAction, ClipboardTimer};
+use crate::file_utils::download_file;
+use crate::password_utils::format_duration;
+use crate::qr_code::QrCode;
+use crate::readable_password::ReadablePassword;
+use crate::reveal::RevealState;
+use crate::select::Select;
 use crate::slider::Slider;
+use crate::ssh_key::derive_ssh_key;
+use crate::storage::LocalStore;
 use crate::switch::Switch;
 use crate::text_input::TextInput;
+use crate::worker::{
+    CalibrationResponse, PasswordRequest, PasswordResponse, PasswordWorker, TotpSeedRequest,
+    TotpSeedResponse, WorkerInput, WorkerOutput,
+};
+use gloo::file::callbacks::{read_as_text, FileReader};
+use gloo::file::{File, ObjectUrl};
+use gloo::timers::callback::Interval;
+use gloo::worker::{Spawnable, WorkerBridge};
+use rustedlesspass_core::aliases::Aliases;
+use rustedlesspass_core::fingerprintgen::{
+    fingerprint_calculate, fingerprint_rich, fingerprint_text, icon_emoji, icon_name,
+    MAX_RICH_SLOTS, RICH_SLOTS,
+};
+use rustedlesspass_core::import_export::{
+    export_profiles, export_profiles_encrypted, import_profiles,
+};
+use rustedlesspass_core::mnemonic::MNEMONIC_WORDS;
+use rustedlesspass_core::passgen::Algorithm;
+use rustedlesspass_core::passphrase::MAX_WORDS;
+use rustedlesspass_core::pin::{MAX_PIN_LENGTH, MIN_PIN_LENGTH};
+use rustedlesspass_core::policy::{preset, PRESETS};
+use rustedlesspass_core::preferences::{Preferences, CLIPBOARD_TIMEOUTS, REVEAL_TIMEOUTS};
+use rustedlesspass_core::profiles::Profiles;
+use rustedlesspass_core::render::character_classes;
+use rustedlesspass_core::settings::{
+    active_policy, password_options, update_disabled_characters, Settings, MODE_MNEMONIC,
+    MODE_PASSPHRASE, MODE_PASSWORD, MODE_PIN,
+};
+use rustedlesspass_core::sshkey::SshKeyPair;
+use rustedlesspass_core::strength::{estimate, format_crack_time, Strength};
+use rustedlesspass_core::totp::{otpauth_uri, seconds_remaining, totp, PERIOD};
+use rustedlesspass_core::typo_guard::{FingerprintCheck, TypoGuard};
+use std::collections::HashMap;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
+// Path of the worker script emitted by Trunk for the `password_worker` binary
+const WORKER_PATH: &str = "./password_worker.js";
+
+// Iteration counts offered in the advanced settings, the first one being the LessPass default
+const ITERATION_PRESETS: [u64; 6] = [100000, 200000, 500000, 1000000, 2000000, 5000000];
+
+// Labels of the master password strength scores
+const STRENGTH_LABELS: [&str; 5] = [
+    "Too guessable",
+    "Very guessable",
+    "Somewhat guessable",
+    "Safely unguessable",
+    "Very unguessable",
+];
+
 pub enum Msg {
-    ChangeSettings(Settings),
+    ChangeSettings(Box<Settings>),
+    ChangePreferences(Box<Preferences>),
     SetWebsite(String),
     SetUsername(String),
     SetPassword(String),
+    SetConfirmPassword(String),
+    SetRememberFingerprint(bool),
     GeneratePassword,
+    ConfirmWeakMaster,
+    PasswordReady(PasswordResponse),
     ShowInputPassword,
+    ForgetProfile,
+    SetEncryptExport(bool),
+    ExportProfiles,
+    ImportProfiles(File),
+    ProfilesLoaded(Result<String, String>),
+    SetAliasInput(String),
+    SetAliasSite(String),
+    AddAlias,
+    RemoveAlias(String),
+    ShowTotp,
+    TotpSeedReady(TotpSeedResponse),
+    ShowSshKey,
+    SshKeyReady(u64, Result<SshKeyPair, String>),
+    CopyPrivateKey,
+    PrivateKeyCopied(Result<(), String>),
+    CalibrationReady(CalibrationResponse),
+    Tick,
+    ClipboardResult(ClipboardAction, Result<(), String>),
+    ClipboardTick,
+    RetryClearClipboard,
+    HoldReveal,
+    ReleaseReveal,
+    RevealTick,
+    ToggleCharacterView,
 }
 
 pub struct App {
     settings: Settings,
+    preferences: Preferences,
+    profiles: Profiles,
+    aliases: Aliases,
+    alias_input: String,
+    alias_site: String,
     website: String,
     username: String,
     password: String,
+    confirm_password: String,
     new_password: String,
     fingerprint: Vec<String>,
-    show: u8,
+    typo_guard: TypoGuard,
+    strength: Strength,
+    weak_master_confirmed: bool,
+    ask_weak_master: bool,
+    reveal: RevealState,
+    reveal_clock: Option<Interval>,
+    character_view: bool,
+    worker: Option<WorkerBridge<PasswordWorker>>,
+    request_id: u64,
+    pending_request: Option<u64>,
+    password_error: Option<String>,
     show_input_password: bool,
+    encrypt_export: bool,
+    transfer_status: Option<String>,
+    export_url: Option<ObjectUrl>,
+    file_reader: Option<FileReader>,
+    // Measured time of one iteration, `None` while the worker measures it
+    iteration_times: HashMap<Algorithm, Option<f64>>,
+    totp_seed: Option<Vec<u8>>,
+    pending_totp: Option<u64>,
+    ssh_key: Option<Result<SshKeyPair, String>>,
+    pending_ssh_key: Option<u64>,
+    private_key_copied: Option<Result<(), String>>,
+    now: u64,
+    clock: Option<Interval>,
+    clipboard_timer: Option<ClipboardTimer>,
+    clipboard_result: Option<Result<(), String>>,
+    clear_result: Option<Result<(), String>>,
+    clear_retry: Option<ClearRetry>,
+    copy_fallback: NodeRef,
+    focus_copy_fallback: bool,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
             settings: Settings::load(),
+            preferences: Preferences::load(),
+            profiles: Profiles::load(),
+            aliases: Aliases::load(),
+            alias_input: String::new(),
+            alias_site: String::new(),
             website: String::new(),
             username: String::new(),
             password: String::new(),
+            confirm_password: String::new(),
             new_password: "Generate and copy".to_string(),
             fingerprint: fingerprint_calculate(""),
-            show: 0,
+            typo_guard: TypoGuard::load(),
+            strength: estimate(""),
+            weak_master_confirmed: false,
+            ask_weak_master: false,
+            reveal: RevealState::default(),
+            reveal_clock: None,
+            character_view: false,
+            worker: None,
+            request_id: 0,
+            pending_request: None,
+            password_error: None,
             show_input_password: false,
+            encrypt_export: false,
+            transfer_status: None,
+            export_url: None,
+            file_reader: None,
+            iteration_times: HashMap::new(),
+            totp_seed: None,
+            pending_totp: None,
+            ssh_key: None,
+            pending_ssh_key: None,
+            private_key_copied: None,
+            now: 0,
+            clock: None,
+            clipboard_timer: None,
+            clipboard_result: None,
+            clear_result: None,
+            clear_retry: None,
+            copy_fallback: NodeRef::default(),
+            focus_copy_fallback: false,
         }
     }
 }
@@ -58,49 +211,371 @@ impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self::default()
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut app = Self::default();
+        app.calibrate_derivation(ctx);
+        app
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::ChangeSettings(settings) => {
-                self.settings = settings.clone();
+                // Any change can alter the first password matching the policy
+                self.settings = Settings {
+                    sub_counter: 0,
+                    ..*settings.clone()
+                };
                 self.settings.disabled = update_disabled_characters(&settings);
+                if self.settings.version == 1 {
+                    // LessPass v1 only generates passwords
+                    self.settings.mode = MODE_PASSWORD;
+                }
+                if self.settings.mode == MODE_PIN
+                    && preset(&self.settings.policy).is_some_and(|policy| !policy.accepts_pins())
+                {
+                    self.settings.policy.clear();
+                }
                 self.settings.store();
-                self.show = 0;
+                self.invalidate_password();
+                self.calibrate_derivation(ctx);
+            }
+            Msg::ChangePreferences(preferences) => {
+                // The site, and so its profile, depends on the normalization
+                let renormalized =
+                    preferences.normalize_domain != self.preferences.normalize_domain;
+                self.preferences = *preferences;
+                self.preferences.store();
+                if renormalized {
+                    self.restore_profile();
+                    self.invalidate_password();
+                    self.calibrate_derivation(ctx);
+                }
             }
             Msg::SetWebsite(next_website) => {
                 self.website = next_website;
-                self.show = 0;
+                self.restore_profile();
+                self.invalidate_password();
+                self.calibrate_derivation(ctx);
             }
             Msg::SetUsername(next_username) => {
                 self.username = next_username;
-                self.show = 0;
+                self.restore_profile();
+                self.invalidate_password();
+                self.calibrate_derivation(ctx);
             }
             Msg::SetPassword(next_password) => {
                 self.password = next_password;
                 self.fingerprint = fingerprint_calculate(self.password.clone().as_str());
-                self.show = 0;
+                self.strength = estimate(&self.password);
+                self.weak_master_confirmed = false;
+                self.ask_weak_master = false;
+                self.invalidate_password();
+            }
+            Msg::SetConfirmPassword(confirm_password) => {
+                self.confirm_password = confirm_password;
+                self.password_error = None;
+            }
+            Msg::SetRememberFingerprint(enabled) => {
+                self.typo_guard.enabled = enabled;
+                if !enabled {
+                    self.typo_guard.forget();
+                }
+                self.typo_guard.store();
+                self.confirm_password.clear();
+                self.password_error = None;
             }
             Msg::GeneratePassword => {
-                (self.show, self.new_password) = update_show_state(
-                    self.show,
-                    &self.website,
-                    &self.username,
-                    &self.password,
-                    &self.settings,
-                    &self.new_password,
-                );
+                if self.pending_request.is_some() {
+                    // Clicking while deriving cancels the request
+                    self.cancel_derivation();
+                } else if !self.reveal.is_generated()
+                    && self.needs_confirmation()
+                    && self.confirm_password != self.password
+                {
+                    self.password_error =
+                        Some("The master passwords do not match, check for a typo".to_string());
+                } else if !self.reveal.is_generated()
+                    && self.strength.score == 0
+                    && !self.weak_master_confirmed
+                {
+                    // Every derived password is only as strong as the master password
+                    self.ask_weak_master = true;
+                } else if !self.reveal.is_generated() {
+                    if self.needs_confirmation() {
+                        // The first master password confirmed on this device is remembered
+                        self.typo_guard.remember(&self.fingerprint);
+                        self.typo_guard.store();
+                        self.confirm_password.clear();
+                    }
+                    if !self.website.is_empty() {
+                        self.profiles
+                            .save(&self.site(), &self.username, &self.settings);
+                        self.profiles.store();
+                    }
+                    self.start_derivation(ctx);
+                } else {
+                    let reveal = self.reveal.toggle(self.preferences.reveal_timeout);
+                    self.set_reveal(ctx, reveal);
+                }
+            }
+            Msg::ConfirmWeakMaster => {
+                self.weak_master_confirmed = true;
+                self.ask_weak_master = false;
+                ctx.link().send_message(Msg::GeneratePassword);
+            }
+            Msg::PasswordReady(response) => {
+                // Answers of cancelled or superseded requests are ignored
+                if self.pending_request == Some(response.id) {
+                    self.pending_request = None;
+                    match response.result {
+                        Ok((password, sub_counter)) => {
+                            self.record_sub_counter(sub_counter as u64);
+                            self.new_password = password;
+                            self.clipboard_result = None;
+                            copy_to_clipboard(
+                                &self.new_password,
+                                self.preferences.clipboard_timeout,
+                                ctx.link().callback(|result| {
+                                    Msg::ClipboardResult(ClipboardAction::Copy, result)
+                                }),
+                            );
+                            let reveal = self.reveal.generate();
+                            self.set_reveal(ctx, reveal);
+                        }
+                        Err(error) => self.password_error = Some(error.to_string()),
+                    }
+                }
             }
 
+            Msg::SetAliasInput(alias) => {
+                self.alias_input = alias;
+            }
+            Msg::SetAliasSite(site) => {
+                self.alias_site = site;
+            }
+            Msg::AddAlias => {
+                // Aliases are keyed by registrable domain whatever the normalization says
+                if self.aliases.add(&self.alias_input, &self.alias_site) {
+                    self.aliases.store();
+                    self.alias_input.clear();
+                    self.alias_site.clear();
+                    self.restore_profile();
+                    self.invalidate_password();
+                }
+            }
+            Msg::RemoveAlias(alias) => {
+                self.aliases.remove(&alias);
+                self.aliases.store();
+                self.restore_profile();
+                self.invalidate_password();
+            }
+            Msg::ShowTotp => {
+                if self.pending_totp.is_none() {
+                    let options = password_options(
+                        &self.site(),
+                        &self.username,
+                        &self.password,
+                        &self.settings,
+                    );
+                    self.request_id += 1;
+                    self.pending_totp = Some(self.request_id);
+                    let request = TotpSeedRequest {
+                        id: self.request_id,
+                        options,
+                    };
+                    self.worker(ctx).send(WorkerInput::TotpSeed(request));
+                }
+            }
+            Msg::TotpSeedReady(response) => {
+                if self.pending_totp == Some(response.id) {
+                    self.pending_totp = None;
+                    self.totp_seed = Some(response.seed);
+                    self.now = unix_time();
+                    let link = ctx.link().clone();
+                    self.clock = Some(Interval::new(1000, move || link.send_message(Msg::Tick)));
+                }
+            }
+            Msg::ShowSshKey => {
+                if self.pending_ssh_key.is_none() {
+                    self.request_id += 1;
+                    self.pending_ssh_key = Some(self.request_id);
+                    let id = self.request_id;
+                    derive_ssh_key(
+                        &self.site(),
+                        &self.username,
+                        &self.password,
+                        &self.settings,
+                        ctx.link()
+                            .callback(move |result| Msg::SshKeyReady(id, result)),
+                    );
+                }
+            }
+            Msg::SshKeyReady(id, result) => {
+                // Answers for a previous host, login or master password are ignored
+                if self.pending_ssh_key == Some(id) {
+                    self.pending_ssh_key = None;
+                    self.private_key_copied = None;
+                    self.ssh_key = Some(result);
+                }
+            }
+            Msg::CopyPrivateKey => {
+                if let Some(Ok(key_pair)) = &self.ssh_key {
+                    copy_to_clipboard(
+                        &key_pair.private_key,
+                        self.preferences.clipboard_timeout,
+                        ctx.link().callback(Msg::PrivateKeyCopied),
+                    );
+                }
+            }
+            Msg::PrivateKeyCopied(result) => {
+                self.private_key_copied = Some(result);
+            }
+            Msg::CalibrationReady(response) => {
+                self.iteration_times
+                    .insert(response.algorithm, Some(response.iteration_time));
+            }
+            Msg::Tick => {
+                self.now = unix_time();
+            }
+            Msg::ClipboardResult(ClipboardAction::Copy, result) => {
+                let timeout = self.preferences.clipboard_timeout;
+                // A new copy replaces the previous one, and its countdown
+                self.clipboard_timer = (result.is_ok() && timeout > 0).then(|| {
+                    let on_tick = ctx.link().callback(|_| Msg::ClipboardTick);
+                    ClipboardTimer::start(&self.new_password, timeout, on_tick)
+                });
+                // The password is selected for a manual copy when the clipboard failed
+                self.focus_copy_fallback = result.is_err();
+                self.clipboard_result = Some(result);
+                self.clear_result = None;
+                self.clear_retry = None;
+            }
+            // Results of a clear superseded by a new copy are ignored
+            Msg::ClipboardResult(ClipboardAction::Clear, result)
+                if self.clipboard_timer.is_none() =>
+            {
+                // Browsers refuse the clipboard to a page in the background, so a failed
+                // clear is retried once the page is focused or shown again
+                self.clear_retry = match &result {
+                    Ok(()) => None,
+                    Err(_) => self.clear_retry.take().or_else(|| {
+                        let on_retry = ctx.link().callback(|_| Msg::RetryClearClipboard);
+                        Some(ClearRetry::start(&self.new_password, on_retry))
+                    }),
+                };
+                self.clear_result = Some(result);
+            }
+            Msg::ClipboardResult(ClipboardAction::Clear, _) => {}
+            Msg::RetryClearClipboard => {
+                if let Some(retry) = &self.clear_retry {
+                    retry.retry(
+                        ctx.link().callback(|result| {
+                            Msg::ClipboardResult(ClipboardAction::Clear, result)
+                        }),
+                    );
+                }
+            }
+            Msg::HoldReveal => {
+                let reveal = self.reveal.hold();
+                self.set_reveal(ctx, reveal);
+            }
+            Msg::ReleaseReveal => {
+                let reveal = self.reveal.release();
+                self.set_reveal(ctx, reveal);
+            }
+            Msg::RevealTick => {
+                let reveal = self.reveal.tick();
+                self.set_reveal(ctx, reveal);
+            }
+            Msg::ToggleCharacterView => {
+                self.character_view = !self.character_view;
+            }
+            Msg::ClipboardTick => {
+                if let Some(timer) = &mut self.clipboard_timer {
+                    if timer.tick() {
+                        if let Some(timer) = self.clipboard_timer.take() {
+                            timer.expire(ctx.link().callback(|result| {
+                                Msg::ClipboardResult(ClipboardAction::Clear, result)
+                            }));
+                        }
+                    }
+                }
+            }
             Msg::ShowInputPassword => {
                 self.show_input_password = !self.show_input_password;
             }
+            Msg::ForgetProfile => {
+                self.profiles.remove(&self.site(), &self.username);
+                self.profiles.store();
+            }
+            Msg::SetEncryptExport(encrypt_export) => {
+                self.encrypt_export = encrypt_export;
+            }
+            Msg::ExportProfiles => {
+                if self.encrypt_export && self.password.is_empty() {
+                    self.transfer_status =
+                        Some("Enter your master password to encrypt the export".to_string());
+                } else {
+                    let contents = if self.encrypt_export {
+                        export_profiles_encrypted(&self.profiles, &self.aliases, &self.password)
+                    } else {
+                        export_profiles(&self.profiles, &self.aliases)
+                    };
+                    self.export_url =
+                        Some(download_file("rustedlesspass-profiles.json", &contents));
+                    self.transfer_status =
+                        Some(format!("Exported {} profiles", self.profiles.entries.len()));
+                }
+            }
+            Msg::ImportProfiles(file) => {
+                let link = ctx.link().clone();
+                self.file_reader = Some(read_as_text(&file, move |contents| {
+                    link.send_message(Msg::ProfilesLoaded(contents.map_err(|e| e.to_string())))
+                }));
+            }
+            Msg::ProfilesLoaded(contents) => {
+                self.file_reader = None;
+                let import = contents.and_then(|contents| {
+                    import_profiles(&contents, &self.password).map_err(|e| e.to_string())
+                });
+                self.transfer_status = Some(match import {
+                    Ok(import) => {
+                        for profile in &import.profiles {
+                            self.profiles
+                                .save(&profile.site, &profile.login, &profile.settings);
+                        }
+                        for alias in &import.aliases {
+                            self.aliases.add(&alias.alias, &alias.site);
+                        }
+                        self.profiles.store();
+                        self.aliases.store();
+                        self.restore_profile();
+                        self.invalidate_password();
+                        match import.skipped {
+                            0 => format!("Imported {} profiles", import.profiles.len()),
+                            skipped => format!(
+                                "Imported {} profiles, skipped {} unsupported ones",
+                                import.profiles.len(),
+                                skipped
+                            ),
+                        }
+                    }
+                    Err(error) => error,
+                });
+            }
         };
         true
     }
 
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.focus_copy_fallback {
+            self.focus_copy_fallback = false;
+            if let Some(input) = self.copy_fallback.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let on_website_change = ctx.link().callback(Msg::SetWebsite);
         let on_username_change = ctx.link().callback(Msg::SetUsername);
@@ -113,8 +588,182 @@ impl Component for App {
             e.prevent_default();
             Msg::GeneratePassword
         });
+        // The outcome of the copy is shown along with the generated password
+        let clipboard_result = self
+            .clipboard_result
+            .as_ref()
+            .filter(|_| self.reveal.is_generated());
+        let on_copy_fallback_focus = Callback::from(|e: FocusEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            input.select();
+        });
+        let on_add_alias_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::AddAlias
+        });
+        let on_forget_profile_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::ForgetProfile
+        });
+        let has_profile = self.profiles.find(&self.site(), &self.username).is_some();
+        let on_encrypt_export_change = ctx
+            .link()
+            .callback(|value: u64| Msg::SetEncryptExport(value != 0));
+        let on_export_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::ExportProfiles
+        });
+        let on_import_change = ctx.link().batch_callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let file = input.files().and_then(|files| files.get(0));
+            input.set_value("");
+            file.map(|file| Msg::ImportProfiles(File::from(file)))
+        });
 
-        let Self { ref settings, .. } = *self;
+        let algorithm_options = vec![
+            (256, "SHA-256".to_string()),
+            (384, "SHA-384".to_string()),
+            (512, "SHA-512".to_string()),
+        ];
+        let mut iteration_options: Vec<(u64, String)> = ITERATION_PRESETS
+            .iter()
+            .map(|&iterations| (iterations, iterations.to_string()))
+            .collect();
+        if !ITERATION_PRESETS.contains(&self.settings.iterations) {
+            // Keep iteration counts of imported profiles selectable
+            iteration_options.push((
+                self.settings.iterations,
+                self.settings.iterations.to_string(),
+            ));
+        }
+        let on_excluded_characters_change = {
+            let settings = self.settings.clone();
+            ctx.link().callback(move |excluded_characters| {
+                Msg::ChangeSettings(Box::new(Settings {
+                    excluded_characters,
+                    ..settings.clone()
+                }))
+            })
+        };
+        let on_separator_change = {
+            let settings = self.settings.clone();
+            ctx.link().callback(move |separator| {
+                Msg::ChangeSettings(Box::new(Settings {
+                    separator,
+                    ..settings.clone()
+                }))
+            })
+        };
+        let on_allowed_symbols_change = {
+            let settings = self.settings.clone();
+            ctx.link().callback(move |allowed_symbols| {
+                Msg::ChangeSettings(Box::new(Settings {
+                    allowed_symbols,
+                    ..settings.clone()
+                }))
+            })
+        };
+        let enabled_classes = [
+            self.settings.lowercase,
+            self.settings.uppercase,
+            self.settings.numbers,
+            self.settings.symbols,
+        ]
+        .iter()
+        .filter(|&&enabled| enabled != 0)
+        .count();
+        let has_exhausted_class = character_classes(&password_options(
+            &self.site(),
+            &self.username,
+            &self.password,
+            &self.settings,
+        ))
+        .len()
+            < enabled_classes;
+        let policy_options: Vec<(u64, String)> = std::iter::once((0, "None".to_string()))
+            .chain(
+                PRESETS
+                    .iter()
+                    .enumerate()
+                    // A PIN cannot meet a policy requiring a leading letter
+                    .filter(|(_, (_, policy))| {
+                        self.settings.mode != MODE_PIN || policy.accepts_pins()
+                    })
+                    .map(|(index, (name, _))| (index as u64 + 1, name.to_string())),
+            )
+            .collect();
+        let policy_value = PRESETS
+            .iter()
+            .position(|(name, _)| *name == self.settings.policy)
+            .map_or(0, |index| index as u64 + 1);
+        let on_policy_change = {
+            let settings = self.settings.clone();
+            ctx.link().callback(move |value: u64| {
+                let policy = match value {
+                    0 => String::new(),
+                    value => PRESETS
+                        .get(value as usize - 1)
+                        .map_or(String::new(), |(name, _)| name.to_string()),
+                };
+                Msg::ChangeSettings(Box::new(Settings {
+                    policy,
+                    ..settings.clone()
+                }))
+            })
+        };
+        let rich_fingerprint = (self.preferences.rich_fingerprint != 0)
+            .then(|| fingerprint_rich(&self.password, self.preferences.rich_fingerprint as usize));
+        let fingerprint_icons: Vec<&str> = match &rich_fingerprint {
+            Some(icons) => icons.iter().map(|icon| icon.icon).collect(),
+            None => self.fingerprint.iter().map(String::as_str).collect(),
+        };
+        let fingerprint_emoji = fingerprint_icons
+            .iter()
+            .filter_map(|icon| icon_emoji(icon))
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let on_confirm_password_change = ctx.link().callback(Msg::SetConfirmPassword);
+        let on_remember_fingerprint_change = ctx
+            .link()
+            .callback(|value: u64| Msg::SetRememberFingerprint(value != 0));
+        let on_confirm_weak_master_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::ConfirmWeakMaster
+        });
+        let on_show_totp_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::ShowTotp
+        });
+        let totp_digits = self.settings.totp_digits as u32;
+        let totp_code = self.totp_seed.as_ref().map(|seed| {
+            (
+                totp(seed, self.now, totp_digits),
+                otpauth_uri(seed, &self.site(), &self.username, totp_digits),
+            )
+        });
+        let on_show_ssh_key_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::ShowSshKey
+        });
+        let on_copy_private_key_click = ctx.link().callback(|e: MouseEvent| {
+            e.prevent_default();
+            Msg::CopyPrivateKey
+        });
+        let is_legacy = self.settings.version == 1;
+        // Policies do not apply to passphrases and mnemonics
+        let has_policy_mode = matches!(self.settings.mode, MODE_PASSWORD | MODE_PIN);
+        let estimated_time = self
+            .iteration_times
+            .get(&Algorithm::from_digest_size(self.settings.algorithm))
+            .copied()
+            .flatten()
+            .map(|time| format_duration(time * self.settings.iterations as f64));
+
+        let Self {
+            ref settings,
+            ref preferences,
+            ..
+        } = *self;
 
         macro_rules! settings_callback {
             ($link:expr, $settings:ident; $key:ident as $ty:ty) => {{
@@ -122,7 +771,7 @@ impl Component for App {
                 $link.callback(move |value| {
                     let mut settings = settings.clone();
                     settings.$key = value as $ty;
-                    Msg::ChangeSettings(settings)
+                    Msg::ChangeSettings(Box::new(settings))
                 })
             }};
             ($link:expr, $settings:ident; $key:ident) => {
@@ -130,6 +779,17 @@ impl Component for App {
             }
         }
 
+        macro_rules! preferences_callback {
+            ($link:expr, $preferences:ident; $key:ident) => {{
+                let preferences = $preferences.clone();
+                $link.callback(move |value| {
+                    let mut preferences = preferences.clone();
+                    preferences.$key = value;
+                    Msg::ChangePreferences(Box::new(preferences))
+                })
+            }};
+        }
+
         html! {
             <body>
             <nav class="container-fluid">
@@ -165,6 +825,9 @@ impl Component for App {
                     <form onsubmit={on_submit}>
                     <TextInput value={self.website.clone()} input_type={"text"} name={"Website"} autocomplete={"off"}
                         on_change={on_website_change} />
+                    if !self.website.is_empty() && self.site() != self.website {
+                        <small>{format!("Passwords are generated for {}", self.site())}</small>
+                    }
                     <TextInput value={self.username.clone()} input_type={"text"} name={"Username"} autocomplete={"email,username"}
                         on_change={on_username_change} />
                     <fieldset role="group">
@@ -172,19 +835,90 @@ impl Component for App {
                         name={"Password"} autocomplete={"current-password"} on_change={on_password_change} />
                         <button
                             style="white-space: nowrap;margin-left: 0rem; padding-left: 0.5rem; padding-right: 0.5rem; align-self: center;"
-                            onclick={on_password_click}>
+                            onclick={on_password_click} title="Show or hide the master password">
+                            if let Some(icons) = &rich_fingerprint {
+                            { for icons.iter().map(|icon| html! {
+                                <i class={format!("fa fa-fw {}", icon.icon)} role="img" aria-label={icon_name(icon.icon)}
+                                    style={icon.color.map(|color| format!("margin: 0 0.2rem;color: {};", color))}></i>
+                            }) }
+                            } else {
                             <i class={match self.fingerprint.first() { Some(s)=> format!("fa fa-fw {}", s),
                             None => String::new(),
-                            }} style="margin-right: 0.2rem;"></i>
+                            }} role="img" aria-label={self.fingerprint.first().and_then(|s| icon_name(s))}
+                                style="margin-right: 0.2rem;"></i>
                             <i class={match self.fingerprint.get(1) { Some(s)=> format!("fa fa-fw {}", s),
                             None => String::new(),
-                            }} style="margin-left: 0.2rem;margin-right: 0.2rem;p"></i>
+                            }} role="img" aria-label={self.fingerprint.get(1).and_then(|s| icon_name(s))}
+                                style="margin-left: 0.2rem;margin-right: 0.2rem;p"></i>
                             <i class={match self.fingerprint.last() { Some(s)=> format!("fa fa-fw {}", s),
                             None => String::new(),
-                            }} style="margin-left: 0.2rem;"></i>
+                            }} role="img" aria-label={self.fingerprint.last().and_then(|s| icon_name(s))}
+                                style="margin-left: 0.2rem;"></i>
+                            }
                         </button>
                     </fieldset>
+                    if preferences.text_fingerprint != 0 {
+                        <small>{format!("Fingerprint: {} ({})", fingerprint_text(&fingerprint_icons), fingerprint_emoji)}</small>
+                    }
+                    if self.needs_confirmation() {
+                        <TextInput value={self.confirm_password.clone()} input_type={"password"} name={"Confirm password"}
+                            autocomplete={"off"} on_change={on_confirm_password_change} />
+                    }
+                    if !self.password.is_empty() {
+                        {match self.typo_guard.check(&self.fingerprint) {
+                            FingerprintCheck::Match => html! {
+                                <small><i class="fa fa-fw fa-check"></i>{" Fingerprint matches the remembered one"}</small>
+                            },
+                            FingerprintCheck::Mismatch => html! {
+                                <small><i class="fa fa-fw fa-triangle-exclamation"></i>
+                                    {" Fingerprint differs from the remembered one, check for a typo"}</small>
+                            },
+                            FingerprintCheck::Unknown => html! {},
+                        }}
+                        <progress value={(self.strength.score + 1).to_string()} max={STRENGTH_LABELS.len().to_string()} />
+                        <small>
+                            {format!("{}: about {} to crack offline.", STRENGTH_LABELS[self.strength.score as usize],
+                                format_crack_time(self.strength.crack_time_seconds))}
+                            if let Some(warning) = self.strength.warning() {
+                                {format!(" {}.", warning)}
+                            }
+                        </small>
+                    }
                     <fieldset>
+                        <Select label="Output" onchange={settings_callback!(ctx.link(), settings; mode)}
+                            value={settings.mode} disabled={is_legacy}
+                            options={vec![(MODE_PASSWORD, "Password".to_string()), (MODE_PASSPHRASE, "Passphrase".to_string()),
+                                (MODE_PIN, "PIN".to_string()), (MODE_MNEMONIC, "Mnemonic (BIP-39)".to_string())]} />
+                        if settings.mode == MODE_PASSPHRASE {
+                        <div class="grid" style="padding: 0rem;">
+                        <TextInput value={settings.separator.clone()} input_type={"text"} name={"Separator"}
+                            autocomplete={"off"} required={false} on_change={on_separator_change} />
+                        <Switch label="Capitalize" onchange={settings_callback!(ctx.link(), settings; capitalize)}
+                            value={settings.capitalize} value_disabled={false} />
+                        </div>
+                        <div class="grid" style="padding: 0rem;">
+                        <Slider label="Words" max={MAX_WORDS as u64} min=1 onchange={settings_callback!(ctx.link(), settings; words)}
+                            value={settings.words} />
+                        <Slider label="Counter" max=100 min=1 onchange={settings_callback!(ctx.link(), settings; counter)}
+                            value={settings.counter} />
+                        </div>
+                        } else if settings.mode == MODE_PIN {
+                        <div class="grid" style="padding: 0rem;">
+                        <Slider label="Digits" max={MAX_PIN_LENGTH as u64} min={MIN_PIN_LENGTH as u64}
+                            onchange={settings_callback!(ctx.link(), settings; size)}
+                            value={settings.size.clamp(MIN_PIN_LENGTH as u64, MAX_PIN_LENGTH as u64)} />
+                        <Slider label="Counter" max=100 min=1 onchange={settings_callback!(ctx.link(), settings; counter)}
+                            value={settings.counter} />
+                        </div>
+                        } else if settings.mode == MODE_MNEMONIC {
+                        <div class="grid" style="padding: 0rem;">
+                        <Select label="Words" onchange={settings_callback!(ctx.link(), settings; mnemonic_words)}
+                            value={settings.mnemonic_words}
+                            options={MNEMONIC_WORDS.iter().map(|&words| (words as u64, words.to_string())).collect::<Vec<_>>()} />
+                        <Slider label="Counter" max=100 min=1 onchange={settings_callback!(ctx.link(), settings; counter)}
+                            value={settings.counter} />
+                        </div>
+                        } else {
                         <nav>
                         <Switch label="a-z" onchange={settings_callback!(ctx.link(), settings; lowercase)}
                             value={settings.lowercase} value_disabled={self.settings.disabled.clone() == "a-z"} />
@@ -201,10 +935,194 @@ impl Component for App {
                         <Slider label="Counter" max=100 min=1 onchange={settings_callback!(ctx.link(), settings; counter)}
                             value={settings.counter} />
                         </div>
+                        }
                     </fieldset>
-                    <button type="submit" class="contrast">{if self.show == 0 {"Generate and copy"} else if self.show == 1
-                        {"**************"} else {self.new_password.as_str()}}</button>
+                    <details>
+                        <summary>{"Advanced"}</summary>
+                        <Select label="Fingerprint" onchange={preferences_callback!(ctx.link(), preferences; rich_fingerprint)}
+                            value={preferences.rich_fingerprint}
+                            options={vec![(0, "Three icons (LessPass)".to_string()),
+                                (RICH_SLOTS as u64, "Three colored icons".to_string()),
+                                (MAX_RICH_SLOTS as u64, "Four colored icons".to_string())]} />
+                        <Switch label="Show the fingerprint as text" onchange={preferences_callback!(ctx.link(), preferences; text_fingerprint)}
+                            value={preferences.text_fingerprint} value_disabled={false} />
+                        <Select label="Clear the clipboard" onchange={preferences_callback!(ctx.link(), preferences; clipboard_timeout)}
+                            value={preferences.clipboard_timeout}
+                            options={CLIPBOARD_TIMEOUTS.iter().map(|&timeout| (timeout, match timeout {
+                                0 => "Never".to_string(),
+                                timeout => format!("After {} seconds", timeout),
+                            })).collect::<Vec<_>>()} />
+                        <Select label="Mask the password again" onchange={preferences_callback!(ctx.link(), preferences; reveal_timeout)}
+                            value={preferences.reveal_timeout}
+                            options={REVEAL_TIMEOUTS.iter().map(|&timeout| (timeout, match timeout {
+                                0 => "Never".to_string(),
+                                timeout => format!("After {} seconds", timeout),
+                            })).collect::<Vec<_>>()} />
+                        <Switch label="Normalize the website to its domain" onchange={preferences_callback!(ctx.link(), preferences; normalize_domain)}
+                            value={preferences.normalize_domain} value_disabled={false} />
+                        <Switch label="Remember the fingerprint on this device" onchange={on_remember_fingerprint_change}
+                            value={self.typo_guard.enabled as u64} value_disabled={false} />
+                        <Select label="Version" onchange={settings_callback!(ctx.link(), settings; version)}
+                            value={settings.version}
+                            options={vec![(2, "LessPass v2".to_string()), (1, "LessPass v1 (legacy)".to_string())]} />
+                        <div class="grid" style="padding: 0rem;">
+                        <Select label="Algorithm" onchange={settings_callback!(ctx.link(), settings; algorithm)}
+                            value={settings.algorithm} options={algorithm_options} disabled={is_legacy} />
+                        <Select label="Iterations" onchange={settings_callback!(ctx.link(), settings; iterations)}
+                            value={settings.iterations} options={iteration_options} disabled={is_legacy} />
+                        </div>
+                        <div class="grid" style="padding: 0rem;">
+                        <TextInput value={settings.excluded_characters.clone()} input_type={"text"} name={"Excluded characters"}
+                            autocomplete={"off"} required={false} on_change={on_excluded_characters_change} />
+                        <TextInput value={settings.allowed_symbols.clone()} input_type={"text"} name={"Allowed symbols"}
+                            autocomplete={"off"} required={false} on_change={on_allowed_symbols_change} />
+                        </div>
+                        <Switch label="No look-alike characters (0 O 1 l I |)" onchange={settings_callback!(ctx.link(), settings; no_lookalikes)}
+                            value={settings.no_lookalikes} value_disabled={false} />
+                        if has_policy_mode {
+                        <Select label="Policy" onchange={on_policy_change} value={policy_value} options={policy_options} />
+                        if let Some(policy) = preset(&settings.policy) {
+                            <small>{policy.to_string()}</small>
+                        }
+                        }
+                        if has_exhausted_class {
+                            <small>{"Every character of an enabled class is excluded, so the password will not contain that class"}</small>
+                        }
+                        if is_legacy {
+                            <small>{"LessPass v1 only generates passwords, always with SHA-256, 8192 iterations and no custom characters"}</small>
+                        } else if let Some(estimated_time) = estimated_time {
+                            <small>{format!("Estimated derivation time on this device: {}", estimated_time)}</small>
+                        }
+                    </details>
+                    if self.ask_weak_master {
+                        <small>{"This master password is very weak, and so is every password derived from it."}</small>
+                        <button class="secondary" onclick={on_confirm_weak_master_click}>{"Use it anyway"}</button>
+                    }
+                    <button type="submit" class="contrast" aria-busy={self.pending_request.map(|_| "true")}
+                        title={self.pending_request.map(|_| "Click to cancel")}>
+                        {if self.pending_request.is_some() {"Deriving…"} else if !self.reveal.is_generated() {"Generate and copy"}
+                        else if !self.reveal.is_revealed() {"**************"} else {self.new_password.as_str()}}
+                        if let Some(timer) = &self.clipboard_timer {
+                            {format!(" (clipboard cleared in {} s)", timer.remaining())}
+                        }
+                        </button>
+                    if let Some(error) = &self.password_error {
+                        <small>{error}</small>
+                    }
+                    if self.reveal.is_generated() {
+                        <div class="grid" style="padding: 0rem;">
+                        <button type="button" class="secondary outline"
+                            onmousedown={ctx.link().callback(|_| Msg::HoldReveal)}
+                            onmouseup={ctx.link().callback(|_| Msg::ReleaseReveal)}
+                            onmouseleave={ctx.link().callback(|_| Msg::ReleaseReveal)}
+                            ontouchstart={ctx.link().callback(|_| Msg::HoldReveal)}
+                            ontouchend={ctx.link().callback(|_| Msg::ReleaseReveal)}>{"Hold to reveal"}</button>
+                        <button type="button" class="secondary outline" aria-pressed={self.character_view.to_string()}
+                            onclick={ctx.link().callback(|_| Msg::ToggleCharacterView)}>{"Character by character"}</button>
+                        </div>
+                    }
+                    if self.character_view && self.reveal.is_revealed() {
+                        <ReadablePassword password={self.new_password.clone()} />
+                    }
+                    if let RevealState::Revealed { remaining: Some(remaining) } = self.reveal {
+                        <small>{format!("Masked again in {} s", remaining)}</small>
+                    }
+                    if let Some(Err(error)) = clipboard_result {
+                        <small>{format!("Could not copy the password ({}), copy it manually:", error)}</small>
+                        <input type="text" readonly=true value={self.new_password.clone()}
+                            ref={self.copy_fallback.clone()} onfocus={on_copy_fallback_focus} />
+                    } else if let Some(Err(error)) = &self.clear_result {
+                        <small>{format!("Could not clear the clipboard ({}), retrying when this page is focused again", error)}</small>
+                    } else if let Some(Ok(())) = clipboard_result {
+                        <small>{"Copied to the clipboard"}</small>
+                    }
+                    if has_profile {
+                        <button class="secondary outline" onclick={on_forget_profile_click}>{"Forget saved profile"}</button>
+                    }
                     </form>
+                    <details>
+                        <summary>{"Profiles"}</summary>
+                        <Switch label="Encrypt with master password" onchange={on_encrypt_export_change}
+                            value={self.encrypt_export as u64} value_disabled={false} />
+                        <div class="grid" style="padding: 0rem;">
+                        <button class="secondary" onclick={on_export_click}>{"Export"}</button>
+                        <label role="button" class="secondary">{"Import"}
+                            <input type="file" accept=".json,application/json" hidden=true onchange={on_import_change} />
+                        </label>
+                        </div>
+                        if let Some(status) = &self.transfer_status {
+                            <small>{status}</small>
+                        }
+                    </details>
+                    <details>
+                        <summary>{"Site aliases"}</summary>
+                        <div class="grid" style="padding: 0rem;">
+                        <TextInput value={self.alias_input.clone()} input_type={"text"} name={"Alias"} autocomplete={"off"}
+                            required={false} on_change={ctx.link().callback(Msg::SetAliasInput)} />
+                        <TextInput value={self.alias_site.clone()} input_type={"text"} name={"Same password as"} autocomplete={"off"}
+                            required={false} on_change={ctx.link().callback(Msg::SetAliasSite)} />
+                        </div>
+                        <button class="secondary" onclick={on_add_alias_click}>{"Add alias"}</button>
+                        { for self.aliases.groups().into_iter().map(|(site, aliases)| html! {
+                            <article>
+                                <strong>{site}</strong>
+                                { for aliases.into_iter().map(|alias| {
+                                    let removed = alias.to_string();
+                                    let on_remove_click = ctx.link().callback(move |e: MouseEvent| {
+                                        e.prevent_default();
+                                        Msg::RemoveAlias(removed.clone())
+                                    });
+                                    html! {
+                                        <div class="grid" style="padding: 0rem;align-items: center;">
+                                            <span>{alias}</span>
+                                            <button class="secondary outline" onclick={on_remove_click}>{"Remove"}</button>
+                                        </div>
+                                    }
+                                }) }
+                            </article>
+                        }) }
+                    </details>
+                    <details>
+                        <summary>{"Two-factor codes (TOTP)"}</summary>
+                        <Select label="Digits" onchange={settings_callback!(ctx.link(), settings; totp_digits)}
+                            value={settings.totp_digits} options={vec![(6, "6".to_string()), (8, "8".to_string())]} />
+                        if let Some((code, uri)) = totp_code {
+                            <p><strong>{code}</strong></p>
+                            <progress value={seconds_remaining(self.now).to_string()} max={PERIOD.to_string()} />
+                            <small>{format!("Renews in {} s", seconds_remaining(self.now))}</small>
+                            <QrCode data={uri.clone()} label={"QR code enrolling the TOTP seed"} />
+                            <small><code>{uri}</code></small>
+                        } else {
+                            <button class="secondary" onclick={on_show_totp_click}
+                                aria-busy={self.pending_totp.map(|_| "true")}
+                                disabled={self.website.is_empty() || self.password.is_empty()}>{"Show code"}</button>
+                        }
+                    </details>
+                    if is_tauri() {
+                        <details>
+                            <summary>{"SSH key"}</summary>
+                            <small>{format!("Host: {}", self.site())}</small>
+                            <small>{format!("Counter: {}", settings.counter)}</small>
+                            if let Some(Ok(key_pair)) = &self.ssh_key {
+                                <label>{"Public key"}</label>
+                                <small><code>{key_pair.public_key.clone()}</code></small>
+                                <label>{"Private key"}</label>
+                                <textarea readonly=true rows="8" value={key_pair.private_key.clone()} />
+                                <button class="secondary" onclick={on_copy_private_key_click}>{"Copy private key"}</button>
+                                if let Some(Err(error)) = &self.private_key_copied {
+                                    <small>{format!("Could not copy the private key ({}), copy it manually", error)}</small>
+                                } else if let Some(Ok(())) = self.private_key_copied {
+                                    <small>{"Copied to the clipboard"}</small>
+                                }
+                            } else if let Some(Err(error)) = &self.ssh_key {
+                                <small>{format!("Could not derive the SSH key: {}", error)}</small>
+                            } else {
+                                <button class="secondary" onclick={on_show_ssh_key_click}
+                                    aria-busy={self.pending_ssh_key.map(|_| "true")}
+                                    disabled={self.website.is_empty() || self.password.is_empty()}>{"Show key"}</button>
+                            }
+                        </details>
+                    }
                 </div>
                 </article>
             </main>
@@ -220,3 +1138,119 @@ impl Component for App {
         }
     }
 }
+
+impl App {
+    // Restores the settings saved for the current website/login pair, or the stored ones
+    fn restore_profile(&mut self) {
+        self.settings = self
+            .profiles
+            .settings_for(&self.site(), &self.username, &Settings::load());
+    }
+
+    // Returns the site the passwords and profiles are keyed by, after its aliases
+    fn site(&self) -> String {
+        let website = self.preferences.normalize(&self.website);
+        self.aliases.resolve(&website).to_string()
+    }
+
+    // Asks the worker to measure the derivation time of the selected algorithm, once per algorithm
+    fn calibrate_derivation(&mut self, ctx: &Context<Self>) {
+        let algorithm = Algorithm::from_digest_size(self.settings.algorithm);
+        if self.settings.version == 1 || self.iteration_times.contains_key(&algorithm) {
+            return;
+        }
+        self.iteration_times.insert(algorithm, None);
+        self.worker(ctx).send(WorkerInput::Calibrate(algorithm));
+    }
+
+    // Returns the password worker, spawning it if needed
+    fn worker(&mut self, ctx: &Context<Self>) -> &WorkerBridge<PasswordWorker> {
+        let link = ctx.link().clone();
+        self.worker.get_or_insert_with(|| {
+            PasswordWorker::spawner()
+                .callback(move |output| {
+                    link.send_message(match output {
+                        WorkerOutput::Password(response) => Msg::PasswordReady(response),
+                        WorkerOutput::TotpSeed(response) => Msg::TotpSeedReady(response),
+                        WorkerOutput::Calibration(response) => Msg::CalibrationReady(response),
+                    })
+                })
+                .spawn(WORKER_PATH)
+        })
+    }
+
+    // Tells whether the master password must be typed twice before its fingerprint is remembered
+    fn needs_confirmation(&self) -> bool {
+        self.typo_guard.enabled && !self.typo_guard.is_remembered()
+    }
+
+    // Sends the current inputs to the password worker
+    fn start_derivation(&mut self, ctx: &Context<Self>) {
+        self.request_id += 1;
+        self.pending_request = Some(self.request_id);
+        self.password_error = None;
+        let request = PasswordRequest {
+            id: self.request_id,
+            options: password_options(&self.site(), &self.username, &self.password, &self.settings),
+            policy: active_policy(&self.settings).cloned(),
+        };
+        self.worker(ctx).send(WorkerInput::Password(request));
+    }
+
+    // Records the sub-counter of the password matching the policy, so it stays reproducible
+    fn record_sub_counter(&mut self, sub_counter: u64) {
+        if self.settings.sub_counter == sub_counter {
+            return;
+        }
+        // The sub-counter only holds for this site, so it is kept out of the stored settings
+        self.settings.sub_counter = sub_counter;
+        if !self.website.is_empty() {
+            self.profiles
+                .save(&self.site(), &self.username, &self.settings);
+            self.profiles.store();
+        }
+    }
+
+    // Cancels the pending derivations, if any
+    fn cancel_derivation(&mut self) {
+        let pending_totp = self.pending_totp.take();
+        if self.pending_request.take().is_some() || pending_totp.is_some() {
+            // A worker cannot be interrupted mid-derivation: drop it so the next
+            // request runs on a fresh worker instead of queuing behind the stale one
+            self.worker = None;
+            // Calibrations the dropped worker had not answered are asked again
+            self.iteration_times.retain(|_, time| time.is_some());
+        }
+    }
+
+    // Moves to a reveal state, ticking every second while it counts down
+    fn set_reveal(&mut self, ctx: &Context<Self>, reveal: RevealState) {
+        if !reveal.is_counting() {
+            self.reveal_clock = None;
+        } else if self.reveal_clock.is_none() {
+            let link = ctx.link().clone();
+            self.reveal_clock = Some(Interval::new(1000, move || {
+                link.send_message(Msg::RevealTick)
+            }));
+        }
+        self.reveal = reveal;
+    }
+
+    // Forgets the generated password after an input change
+    fn invalidate_password(&mut self) {
+        self.cancel_derivation();
+        self.password_error = None;
+        self.reveal = self.reveal.invalidate();
+        self.reveal_clock = None;
+        self.totp_seed = None;
+        self.clock = None;
+        self.ssh_key = None;
+        self.pending_ssh_key = None;
+        self.private_key_copied = None;
+    }
+}
+
+// Returns the current time, in seconds since the Unix epoch
+fn unix_time() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
@@ -0,0 +1,17 @@
+/*
+    Entry point of the Web Worker deriving passwords off the UI thread.
+
+    Trunk builds this binary as `password_worker.js`, which the application spawns
+    on startup to calibrate the derivation time.
+*/
+
+#[path = "../worker.rs"]
+mod worker;
+
+use gloo::worker::Registrable;
+use worker::PasswordWorker;
+
+fn main() {
+    console_error_panic_hook::set_once();
+    PasswordWorker::registrar().register();
+}
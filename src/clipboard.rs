@@ -0,0 +1,251 @@
+/*
+    This module contains the clipboard helpers and the timer clearing a copied password.
+
+    The `copy_to_clipboard` function copies a generated password to the clipboard and reports
+    whether it succeeded, checking the `clipboard-write` permission first where it is supported.
+    The `clear_clipboard` function empties the clipboard if it still holds a given value, and
+    reports whether it succeeded. Browsers refuse the clipboard to a page in the background,
+    so the `ClearRetry` struct retries a failed clear once the page is focused or shown again.
+    The `ClipboardTimer` struct counts down the seconds before the copied password is cleared.
+
+    In the Tauri build the clipboard is handled by the native `copy_to_clipboard` command,
+    which also clears it after the timeout, even if the window was closed in the meantime.
+*/
+
+use gloo::events::EventListener;
+use gloo::timers::callback::Interval;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use yew::Callback;
+
+#[wasm_bindgen]
+extern "C" {
+    // Defined by Tauri as `withGlobalTauri` is enabled
+    #[wasm_bindgen(catch, js_namespace = ["window", "__TAURI__", "core"])]
+    pub(crate) async fn invoke(command: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+// Define an enum to tell which clipboard operation a result belongs to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipboardAction {
+    Copy,
+    Clear,
+}
+
+// Define a struct to hold the arguments of the native `copy_to_clipboard` command
+#[derive(Serialize)]
+struct CopyArgs<'a> {
+    text: &'a str,
+    timeout: u64,
+}
+
+// Returns whether the application runs in the Tauri webview
+pub(crate) fn is_tauri() -> bool {
+    web_sys::window()
+        .map(|window| js_sys::Reflect::has(&window, &"__TAURI__".into()).unwrap_or(false))
+        .unwrap_or(false)
+}
+
+// Returns the message of a rejected promise or a thrown error
+pub(crate) fn error_message(error: JsValue) -> String {
+    error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| error.as_string())
+        .unwrap_or_else(|| "unknown error".to_string())
+}
+
+// Returns whether writing to the clipboard was denied, if the browser can tell
+async fn is_write_denied() -> bool {
+    let Some(permissions) =
+        web_sys::window().and_then(|window| window.navigator().permissions().ok())
+    else {
+        return false;
+    };
+    let descriptor = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&descriptor, &"name".into(), &"clipboard-write".into());
+    // Some browsers do not know this permission and reject the query
+    let Ok(query) = permissions.query(&descriptor) else {
+        return false;
+    };
+    match JsFuture::from(query).await {
+        Ok(status) => js_sys::Reflect::get(&status, &"state".into())
+            .ok()
+            .and_then(|state| state.as_string())
+            .is_some_and(|state| state == "denied"),
+        Err(_) => false,
+    }
+}
+
+// Writes text to the clipboard of the browser or of the native build
+async fn write_text(text: &str, timeout: u64) -> Result<(), String> {
+    if is_tauri() {
+        let args =
+            serde_wasm_bindgen::to_value(&CopyArgs { text, timeout }).map_err(|e| e.to_string())?;
+        return invoke("copy_to_clipboard", args)
+            .await
+            .map(|_| ())
+            .map_err(error_message);
+    }
+    let clipboard = web_sys::window()
+        .and_then(|window| window.navigator().clipboard())
+        .ok_or("the clipboard is not available on this page")?;
+    if is_write_denied().await {
+        return Err("the clipboard permission was denied".to_string());
+    }
+    JsFuture::from(clipboard.write_text(text))
+        .await
+        .map(|_| ())
+        .map_err(error_message)
+}
+
+/*
+    Copies a generated password to the clipboard.
+
+    Arguments:
+    - `password`: A string slice representing the generated password.
+    - `timeout`: The seconds before the native build clears the clipboard, 0 to keep it.
+    - `on_result`: A `Callback` emitted with the outcome of the copy.
+*/
+pub fn copy_to_clipboard(password: &str, timeout: u64, on_result: Callback<Result<(), String>>) {
+    let password = password.to_string();
+    spawn_local(async move {
+        on_result.emit(write_text(&password, timeout).await);
+    });
+}
+
+// Empties the clipboard of the browser if it still holds a value
+async fn clear_text(value: &str) -> Result<(), String> {
+    let clipboard = web_sys::window()
+        .and_then(|window| window.navigator().clipboard())
+        .ok_or("the clipboard is not available on this page")?;
+    // Reading is refused while the page is in the background, and the value is then kept
+    let text = JsFuture::from(clipboard.read_text())
+        .await
+        .map_err(error_message)?;
+    if text.as_string().as_deref() == Some(value) {
+        JsFuture::from(clipboard.write_text(""))
+            .await
+            .map_err(error_message)?;
+    }
+    Ok(())
+}
+
+/*
+    Empties the clipboard if it still holds a value, so that anything copied since is kept.
+
+    Arguments:
+    - `value`: A string slice representing the value copied earlier.
+    - `on_result`: A `Callback` emitted with the outcome of the clear.
+*/
+pub fn clear_clipboard(value: &str, on_result: Callback<Result<(), String>>) {
+    let value = value.to_string();
+    spawn_local(async move {
+        on_result.emit(clear_text(&value).await);
+    });
+}
+
+// Define a struct to retry a failed clear once the page is focused or shown again
+pub struct ClearRetry {
+    value: String,
+    _listeners: Vec<EventListener>,
+}
+
+impl ClearRetry {
+    /*
+        Waits for the page to be focused or shown again to retry clearing a value.
+
+        Arguments:
+        - `value`: A string slice representing the value to clear.
+        - `on_retry`: A `Callback` emitted when the page is focused or shown again.
+
+        Returns:
+        A `ClearRetry`, which stops waiting when dropped.
+    */
+    pub fn start(value: &str, on_retry: Callback<()>) -> Self {
+        let mut listeners = Vec::new();
+        if let Some(window) = web_sys::window() {
+            let on_focus = on_retry.clone();
+            listeners.push(EventListener::new(&window, "focus", move |_| {
+                on_focus.emit(())
+            }));
+            if let Some(document) = window.document() {
+                listeners.push(EventListener::new(
+                    &document,
+                    "visibilitychange",
+                    move |_| on_retry.emit(()),
+                ));
+            }
+        }
+        Self {
+            value: value.to_string(),
+            _listeners: listeners,
+        }
+    }
+
+    /*
+        Tries to clear the value again.
+
+        Arguments:
+        - `on_result`: A `Callback` emitted with the outcome of the clear.
+    */
+    pub fn retry(&self, on_result: Callback<Result<(), String>>) {
+        clear_clipboard(&self.value, on_result);
+    }
+}
+
+// Define a struct to count down the seconds before a copied password is cleared
+pub struct ClipboardTimer {
+    value: String,
+    remaining: u64,
+    _interval: Interval,
+}
+
+impl ClipboardTimer {
+    /*
+        Starts counting down the seconds before a copied value is cleared.
+
+        Arguments:
+        - `value`: A string slice representing the copied value.
+        - `timeout`: The number of seconds before the value is cleared.
+        - `on_tick`: A `Callback` emitted every second.
+
+        Returns:
+        A `ClipboardTimer`, which stops counting when dropped.
+    */
+    pub fn start(value: &str, timeout: u64, on_tick: Callback<()>) -> Self {
+        Self {
+            value: value.to_string(),
+            remaining: timeout,
+            _interval: Interval::new(1000, move || on_tick.emit(())),
+        }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /*
+        Counts one second down.
+
+        Returns:
+        `true` once the timeout has elapsed.
+    */
+    pub fn tick(&mut self) -> bool {
+        self.remaining = self.remaining.saturating_sub(1);
+        self.remaining == 0
+    }
+
+    /*
+        Clears the copied value from the clipboard, unless the native build already does.
+
+        Arguments:
+        - `on_result`: A `Callback` emitted with the outcome of the clear.
+    */
+    pub fn expire(self, on_result: Callback<Result<(), String>>) {
+        if !is_tauri() {
+            clear_clipboard(&self.value, on_result);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/*
+    This module contains browser helpers for working with files.
+
+    The `download_file` function offers text contents to the user as a file download.
+*/
+
+use gloo::file::{Blob, ObjectUrl};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
+
+/*
+    Offers text contents to the user as a file download.
+
+    Arguments:
+    - `file_name`: A string slice representing the suggested file name.
+    - `contents`: A string slice containing the file contents.
+
+    Returns:
+    The `ObjectUrl` backing the download, which must be kept alive until the download starts.
+*/
+pub fn download_file(file_name: &str, contents: &str) -> ObjectUrl {
+    let url = ObjectUrl::from(Blob::new_with_options(contents, Some("application/json")));
+
+    let document = web_sys::window()
+        .expect("window")
+        .document()
+        .expect("document");
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .expect("anchor element")
+        .dyn_into()
+        .expect("anchor element");
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    url
+}
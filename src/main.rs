@@ -1,13 +1,19 @@
 #![recursion_limit = "256"]
 
 mod app;
-mod fingerprintgen;
-mod passgen;
+mod clipboard;
+mod file_utils;
 mod password_utils;
-mod settings;
+mod qr_code;
+mod readable_password;
+mod reveal;
+mod select;
 mod slider;
+mod ssh_key;
+mod storage;
 mod switch;
 mod text_input;
+mod worker;
 
 use app::App;
 
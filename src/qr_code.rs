@@ -0,0 +1,37 @@
+/*
+   This module defines a QrCode component for Yew, which renders text such as an
+   `otpauth://` URI as an inline SVG QR code.
+
+   The SVG markup is generated by the qrcode crate from the provided data only.
+*/
+
+use qrcode::render::svg;
+use qrcode::QrCode as Code;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub data: String,
+    pub label: String,
+}
+
+#[function_component(QrCode)]
+pub fn qr_code(props: &Props) -> Html {
+    let Props { data, label } = props.clone();
+
+    let svg = match Code::new(data.as_bytes()) {
+        Ok(code) => code
+            .render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build(),
+        Err(_) => return html! {},
+    };
+
+    html! {
+        <figure role="img" aria-label={label}>
+            { Html::from_html_unchecked(AttrValue::from(svg)) }
+        </figure>
+    }
+}
@@ -0,0 +1,133 @@
+/*
+   This module defines a ReadablePassword component for Yew, which shows a generated password
+   for typing it by hand on another device.
+
+   The characters are grouped in chunks and colored by class, the characters that look alike
+   are named below them, and the password can be spelled with the NATO phonetic alphabet,
+   as text or read aloud by the speech synthesis of the browser. Only the voices of the device
+   are used, as the online voices of some browsers send the text to a remote service, and
+   reading aloud is disabled when the device has none.
+*/
+
+use gloo::events::EventListener;
+use rustedlesspass_core::readable::{
+    ambiguous_name, character_class, chunk_password, spell_password, CharacterClass, CHUNK_SIZE,
+};
+use wasm_bindgen::JsCast;
+use web_sys::{SpeechSynthesis, SpeechSynthesisUtterance, SpeechSynthesisVoice};
+use yew::prelude::*;
+
+// Colors of the character classes, distinguishable with color vision deficiencies
+const CLASS_COLORS: [(CharacterClass, &str, &str); 4] = [
+    (CharacterClass::Lowercase, "inherit", "lowercase"),
+    (CharacterClass::Uppercase, "#0072B2", "uppercase"),
+    (CharacterClass::Digit, "#D55E00", "digit"),
+    (CharacterClass::Symbol, "#009E73", "symbol"),
+];
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub password: AttrValue,
+}
+
+// Returns the color and the name of the class of a character
+fn class_style(character: char) -> (&'static str, &'static str) {
+    let class = character_class(character);
+    CLASS_COLORS
+        .iter()
+        .find(|(other, _, _)| *other == class)
+        .map(|(_, color, name)| (*color, *name))
+        .unwrap_or(("inherit", ""))
+}
+
+// Returns the speech synthesis of the browser, if it supports it
+fn speech_synthesis() -> Option<SpeechSynthesis> {
+    web_sys::window().and_then(|window| window.speech_synthesis().ok())
+}
+
+// Returns a voice running on the device, so that the password never leaves it
+fn local_voice() -> Option<SpeechSynthesisVoice> {
+    speech_synthesis()?
+        .get_voices()
+        .iter()
+        .filter_map(|voice| voice.dyn_into::<SpeechSynthesisVoice>().ok())
+        .find(|voice| voice.local_service())
+}
+
+// Reads the spelled password aloud with a voice of the device
+fn speak(text: &str, voice: &SpeechSynthesisVoice) {
+    if let (Some(synthesis), Ok(utterance)) = (
+        speech_synthesis(),
+        SpeechSynthesisUtterance::new_with_text(text),
+    ) {
+        utterance.set_voice(Some(voice));
+        utterance.set_rate(0.8);
+        synthesis.cancel();
+        synthesis.speak(&utterance);
+    }
+}
+
+#[function_component(ReadablePassword)]
+pub fn readable_password(props: &Props) -> Html {
+    let spelling = spell_password(&props.password);
+    let voice = use_state(local_voice);
+    {
+        let voice = voice.clone();
+        // Some browsers load their voices after the page
+        use_effect_with((), move |_| {
+            let listener = speech_synthesis().map(|synthesis| {
+                EventListener::new(&synthesis, "voiceschanged", move |_| {
+                    voice.set(local_voice())
+                })
+            });
+            move || drop(listener)
+        });
+    }
+    let on_read_aloud_click = {
+        let spelling = spelling.clone();
+        let voice = voice.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(voice) = voice.as_ref() {
+                speak(&spelling, voice);
+            }
+        })
+    };
+
+    html! {
+        <div>
+            <div style="display: flex;flex-wrap: wrap;gap: 1rem;margin-bottom: 0.5rem;font-family: monospace;font-size: 1.5rem;">
+            { for chunk_password(&props.password, CHUNK_SIZE).into_iter().map(|chunk| html! {
+                <span style="display: inline-flex;gap: 0.25rem;">
+                { for chunk.chars().map(|character| {
+                    let (color, class) = class_style(character);
+                    let name = ambiguous_name(character);
+                    html! {
+                        <span style="display: inline-flex;flex-direction: column;align-items: center;min-width: 1.5rem;"
+                            title={class}>
+                            <span style={format!("color: {};", color)}>{character}</span>
+                            if let Some(name) = name {
+                                <small style="font-family: sans-serif;font-size: 0.6rem;">{name}</small>
+                            }
+                        </span>
+                    }
+                }) }
+                </span>
+            }) }
+            </div>
+            <small>
+            { for CLASS_COLORS.iter().map(|(_, color, name)| html! {
+                <span style={format!("color: {};margin-right: 1rem;", color)}>{name}</span>
+            }) }
+            </small>
+            <details>
+                <summary>{"Spelling"}</summary>
+                <p>{spelling}</p>
+                <button type="button" class="secondary outline" onclick={on_read_aloud_click}
+                    disabled={voice.is_none()}>{"Read aloud"}</button>
+                if voice.is_none() {
+                    <small>{"No voice of this device is available to read aloud"}</small>
+                }
+            </details>
+        </div>
+    }
+}
@@ -0,0 +1,213 @@
+/*
+    This module defines the RevealState enum, the state machine deciding whether the
+    generated password is shown.
+
+    A password starts masked once generated. It is revealed by a click, until it is hidden
+    again or its timeout elapses, or held revealed while the reveal button is pressed. Any
+    change of the inputs invalidates it.
+
+    The file also includes unit tests for every transition.
+*/
+
+// Define an enum of the states of the generated password
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RevealState {
+    #[default]
+    NotGenerated,
+    Masked,
+    // Seconds before the password is masked again, if it is masked automatically
+    Revealed {
+        remaining: Option<u64>,
+    },
+    Held,
+}
+
+impl RevealState {
+    pub fn is_generated(self) -> bool {
+        self != RevealState::NotGenerated
+    }
+
+    pub fn is_revealed(self) -> bool {
+        matches!(self, RevealState::Revealed { .. } | RevealState::Held)
+    }
+
+    // Returns whether the state counts seconds down, and needs a tick every second
+    pub fn is_counting(self) -> bool {
+        matches!(self, RevealState::Revealed { remaining: Some(_) })
+    }
+
+    /*
+        Masks a newly generated password.
+    */
+    pub fn generate(self) -> Self {
+        RevealState::Masked
+    }
+
+    /*
+        Reveals a generated password.
+
+        Arguments:
+        - `timeout`: The seconds before the password is masked again, 0 to keep it revealed.
+    */
+    pub fn reveal(self, timeout: u64) -> Self {
+        match self {
+            RevealState::NotGenerated => self,
+            _ => RevealState::Revealed {
+                remaining: (timeout > 0).then_some(timeout),
+            },
+        }
+    }
+
+    pub fn hide(self) -> Self {
+        match self {
+            RevealState::NotGenerated => self,
+            _ => RevealState::Masked,
+        }
+    }
+
+    /*
+        Reveals a masked password and masks a revealed one.
+
+        Arguments:
+        - `timeout`: The seconds before a revealed password is masked again, 0 to keep it.
+    */
+    pub fn toggle(self, timeout: u64) -> Self {
+        match self {
+            RevealState::Masked => self.reveal(timeout),
+            _ => self.hide(),
+        }
+    }
+
+    /*
+        Reveals a masked password while the reveal button is held.
+    */
+    pub fn hold(self) -> Self {
+        match self {
+            RevealState::Masked => RevealState::Held,
+            _ => self,
+        }
+    }
+
+    pub fn release(self) -> Self {
+        match self {
+            RevealState::Held => RevealState::Masked,
+            _ => self,
+        }
+    }
+
+    /*
+        Counts one second down, masking the password once its timeout has elapsed.
+    */
+    pub fn tick(self) -> Self {
+        match self {
+            RevealState::Revealed {
+                remaining: Some(remaining),
+            } if remaining > 1 => RevealState::Revealed {
+                remaining: Some(remaining - 1),
+            },
+            RevealState::Revealed { remaining: Some(_) } => RevealState::Masked,
+            _ => self,
+        }
+    }
+
+    /*
+        Forgets the password after an input change.
+    */
+    pub fn invalidate(self) -> Self {
+        RevealState::NotGenerated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVEALED: RevealState = RevealState::Revealed { remaining: None };
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(RevealState::NotGenerated.generate(), RevealState::Masked);
+        assert_eq!(REVEALED.generate(), RevealState::Masked);
+        assert_eq!(RevealState::Held.generate(), RevealState::Masked);
+    }
+
+    #[test]
+    fn test_reveal() {
+        assert_eq!(RevealState::Masked.reveal(0), REVEALED);
+        assert_eq!(
+            RevealState::Masked.reveal(30),
+            RevealState::Revealed {
+                remaining: Some(30)
+            }
+        );
+        assert_eq!(RevealState::Held.reveal(0), REVEALED);
+        assert_eq!(
+            RevealState::NotGenerated.reveal(0),
+            RevealState::NotGenerated
+        );
+    }
+
+    #[test]
+    fn test_hide() {
+        assert_eq!(REVEALED.hide(), RevealState::Masked);
+        assert_eq!(RevealState::Held.hide(), RevealState::Masked);
+        assert_eq!(RevealState::Masked.hide(), RevealState::Masked);
+        assert_eq!(RevealState::NotGenerated.hide(), RevealState::NotGenerated);
+    }
+
+    #[test]
+    fn test_toggle() {
+        assert_eq!(RevealState::Masked.toggle(0), REVEALED);
+        assert_eq!(REVEALED.toggle(0), RevealState::Masked);
+        assert_eq!(RevealState::Held.toggle(0), RevealState::Masked);
+        assert_eq!(
+            RevealState::NotGenerated.toggle(0),
+            RevealState::NotGenerated
+        );
+    }
+
+    #[test]
+    fn test_hold_and_release() {
+        assert_eq!(RevealState::Masked.hold(), RevealState::Held);
+        assert_eq!(RevealState::Held.release(), RevealState::Masked);
+        assert_eq!(REVEALED.hold(), REVEALED);
+        assert_eq!(REVEALED.release(), REVEALED);
+        assert_eq!(RevealState::NotGenerated.hold(), RevealState::NotGenerated);
+    }
+
+    #[test]
+    fn test_tick_masks_after_timeout() {
+        let mut state = RevealState::Masked.reveal(2);
+        assert!(state.is_counting());
+
+        state = state.tick();
+        assert_eq!(state, RevealState::Revealed { remaining: Some(1) });
+        state = state.tick();
+        assert_eq!(state, RevealState::Masked);
+        assert!(!state.is_counting());
+
+        assert_eq!(REVEALED.tick(), REVEALED);
+        assert_eq!(RevealState::Held.tick(), RevealState::Held);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        for state in [
+            RevealState::NotGenerated,
+            RevealState::Masked,
+            REVEALED,
+            RevealState::Held,
+        ] {
+            assert_eq!(state.invalidate(), RevealState::NotGenerated);
+            assert!(!state.invalidate().is_generated());
+        }
+    }
+
+    #[test]
+    fn test_is_revealed() {
+        assert!(!RevealState::NotGenerated.is_revealed());
+        assert!(!RevealState::Masked.is_revealed());
+        assert!(REVEALED.is_revealed());
+        assert!(RevealState::Held.is_revealed());
+    }
+}
@@ -0,0 +1,75 @@
+/*
+   This module defines a Select component for Yew, which is used to create
+   dropdowns with customizable labels, options, values, and callback functions.
+
+   The Select component utilizes thread-local mutable memory for generating
+   unique IDs and integrates with HTML select elements for input manipulation.
+*/
+
+use std::cell::Cell;
+use web_sys::HtmlSelectElement;
+use yew::events::Event;
+use yew::{html, Callback, Component, Context, Html, Properties, TargetCast};
+
+thread_local! {
+    static SELECT_ID: Cell<usize> = Cell::default();
+}
+
+fn next_select_id() -> usize {
+    SELECT_ID.with(|cell| cell.replace(cell.get() + 1))
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct Props {
+    pub label: &'static str,
+    pub value: u64,
+    pub options: Vec<(u64, String)>,
+    pub onchange: Callback<u64>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+pub struct Select {
+    id: usize,
+}
+
+impl Component for Select {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            id: next_select_id(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props {
+            label,
+            value,
+            ref options,
+            ref onchange,
+            disabled,
+        } = *ctx.props();
+
+        let id = format!("select-{}", self.id);
+
+        let onchange = onchange.reform(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            select.value().parse::<u64>().unwrap_or_default()
+        });
+
+        html! {
+            <label for={id.clone()}>
+                { label }
+                <select {id} {onchange} {disabled}>
+                    { for options.iter().map(|(option_value, option_label)| html! {
+                        <option value={option_value.to_string()} selected={*option_value == value}>
+                            { option_label }
+                        </option>
+                    }) }
+                </select>
+            </label>
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/*
+    This module contains the derivation of SSH key pairs in the native build.
+
+    The `derive_ssh_key` function asks the native `ssh_key` command for the Ed25519 key pair
+    of a host, so that the same master password, host, login and counter give the same key on
+    every machine. The command only exists in the Tauri build, which is checked with `is_tauri`.
+*/
+
+use rustedlesspass_core::settings::Settings;
+use rustedlesspass_core::sshkey::SshKeyPair;
+use serde::Serialize;
+use wasm_bindgen_futures::spawn_local;
+use yew::Callback;
+
+use crate::clipboard::{error_message, invoke};
+
+// Define a struct to hold the arguments of the native `ssh_key` command
+#[derive(Serialize)]
+struct SshKeyArgs<'a> {
+    host: &'a str,
+    username: &'a str,
+    password: &'a str,
+    settings: &'a Settings,
+}
+
+// Asks the native build for the key pair of a host
+async fn invoke_ssh_key(
+    host: &str,
+    username: &str,
+    password: &str,
+    settings: &Settings,
+) -> Result<SshKeyPair, String> {
+    let args = serde_wasm_bindgen::to_value(&SshKeyArgs {
+        host,
+        username,
+        password,
+        settings,
+    })
+    .map_err(|e| e.to_string())?;
+    let key_pair = invoke("ssh_key", args).await.map_err(error_message)?;
+    serde_wasm_bindgen::from_value(key_pair).map_err(|e| e.to_string())
+}
+
+/*
+    Derives the SSH key pair of a host with the native `ssh_key` command.
+
+    Arguments:
+    - `host`: A string slice representing the host, used as the site.
+    - `username`: A string slice representing the login.
+    - `password`: A string slice representing the master password.
+    - `settings`: The `Settings` holding the counter and the key derivation parameters.
+    - `on_result`: A `Callback` emitted with the key pair or the error.
+*/
+pub fn derive_ssh_key(
+    host: &str,
+    username: &str,
+    password: &str,
+    settings: &Settings,
+    on_result: Callback<Result<SshKeyPair, String>>,
+) {
+    let (host, username, password) = (host.to_string(), username.to_string(), password.to_string());
+    let settings = settings.clone();
+    spawn_local(async move {
+        on_result.emit(invoke_ssh_key(&host, &username, &password, &settings).await);
+    });
+}
@@ -0,0 +1,47 @@
+/*
+   This module defines the LocalStore trait, which implements methods to load
+   and store the settings data model of the core crate using local storage.
+
+   Each stored type is saved as JSON under its own local storage key.
+*/
+
+use gloo::storage::{LocalStorage, Storage};
+use rustedlesspass_core::aliases::Aliases;
+use rustedlesspass_core::preferences::Preferences;
+use rustedlesspass_core::profiles::Profiles;
+use rustedlesspass_core::settings::Settings;
+use rustedlesspass_core::typo_guard::TypoGuard;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub trait LocalStore: Serialize + DeserializeOwned + Default {
+    const KEY: &'static str;
+
+    fn load() -> Self {
+        LocalStorage::get(Self::KEY).unwrap_or_default()
+    }
+
+    fn store(&self) {
+        let _ = LocalStorage::set(Self::KEY, self);
+    }
+}
+
+impl LocalStore for Settings {
+    const KEY: &'static str = "rustedlesspass.rustedlesspass.settings";
+}
+
+impl LocalStore for Preferences {
+    const KEY: &'static str = "rustedlesspass.rustedlesspass.preferences";
+}
+
+impl LocalStore for Profiles {
+    const KEY: &'static str = "rustedlesspass.rustedlesspass.profiles";
+}
+
+impl LocalStore for Aliases {
+    const KEY: &'static str = "rustedlesspass.rustedlesspass.aliases";
+}
+
+impl LocalStore for TypoGuard {
+    const KEY: &'static str = "rustedlesspass.rustedlesspass.fingerprint";
+}
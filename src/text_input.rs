@@ -18,6 +18,8 @@ pub struct Props {
     pub name: String,
     pub autocomplete: String,
     pub on_change: Callback<String>,
+    #[prop_or(true)]
+    pub required: bool,
 }
 
 fn get_value_from_input_event(e: InputEvent) -> String {
@@ -35,6 +37,7 @@ pub fn text_input(props: &Props) -> Html {
         name,
         autocomplete,
         on_change,
+        required,
     } = props.clone();
 
     let oninput =
@@ -47,7 +50,7 @@ pub fn text_input(props: &Props) -> Html {
             placeholder={name.clone()}
             aria-label={name}
             autocomplete={autocomplete}
-            required=true
+            required={required}
             value={value}
             oninput={oninput}
         />
@@ -0,0 +1,130 @@
+/*
+    This module defines the PasswordWorker, a Web Worker deriving passwords off the UI thread.
+
+    `generate_password` runs 100,000 PBKDF2-SHA256 iterations, which freezes the page for a
+    noticeable moment on low-end devices when done inside `App::update`. The worker receives
+    a `PasswordRequest` and answers with a `PasswordResponse` carrying the same request id, so
+    the application can ignore the answers of requests it cancelled in the meantime.
+
+    When the request carries a policy, the response also holds the sub-counter of the first
+    password matching it, or the reason why no password matches.
+
+    The worker also derives TOTP seeds, which cost as many PBKDF2 iterations as a password,
+    and times a short derivation to estimate the derivation time of each algorithm.
+*/
+
+use gloo::worker::{HandlerId, Worker, WorkerScope};
+use rustedlesspass_core::passgen::{generate_password, Algorithm, PasswordOptions};
+use rustedlesspass_core::policy::{generate_password_with_policy, Policy, PolicyError};
+use rustedlesspass_core::totp::derive_seed;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PasswordRequest {
+    pub id: u64,
+    pub options: PasswordOptions,
+    pub policy: Option<Policy>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PasswordResponse {
+    pub id: u64,
+    pub result: Result<(String, u32), PolicyError>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TotpSeedRequest {
+    pub id: u64,
+    pub options: PasswordOptions,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TotpSeedResponse {
+    pub id: u64,
+    pub seed: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct CalibrationResponse {
+    pub algorithm: Algorithm,
+    // Time of one iteration, in milliseconds
+    pub iteration_time: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum WorkerInput {
+    Password(PasswordRequest),
+    TotpSeed(TotpSeedRequest),
+    Calibrate(Algorithm),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum WorkerOutput {
+    Password(PasswordResponse),
+    TotpSeed(TotpSeedResponse),
+    Calibration(CalibrationResponse),
+}
+
+// Iterations run to calibrate the derivation time
+const CALIBRATION_ITERATIONS: u32 = 10000;
+
+/*
+    Times a short key derivation to estimate the cost of one PBKDF2 iteration on this device.
+
+    Arguments:
+    - `algorithm`: The `Algorithm` used for the derivation.
+
+    Returns:
+    The measured time of one iteration, in milliseconds.
+*/
+fn measure_iteration_time(algorithm: Algorithm) -> f64 {
+    let options = PasswordOptions {
+        algorithm,
+        iterations: CALIBRATION_ITERATIONS,
+        ..PasswordOptions::default()
+    };
+    let start = js_sys::Date::now();
+    generate_password(options);
+    (js_sys::Date::now() - start) / CALIBRATION_ITERATIONS as f64
+}
+
+pub struct PasswordWorker;
+
+impl Worker for PasswordWorker {
+    type Message = ();
+    type Input = WorkerInput;
+    type Output = WorkerOutput;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        let output = match msg {
+            WorkerInput::Password(request) => {
+                let result = match request.policy {
+                    Some(policy) => generate_password_with_policy(request.options, &policy),
+                    None => {
+                        let sub_counter = request.options.sub_counter;
+                        Ok((generate_password(request.options), sub_counter))
+                    }
+                };
+                WorkerOutput::Password(PasswordResponse {
+                    id: request.id,
+                    result,
+                })
+            }
+            WorkerInput::TotpSeed(request) => WorkerOutput::TotpSeed(TotpSeedResponse {
+                id: request.id,
+                seed: derive_seed(&request.options),
+            }),
+            WorkerInput::Calibrate(algorithm) => WorkerOutput::Calibration(CalibrationResponse {
+                algorithm,
+                iteration_time: measure_iteration_time(algorithm),
+            }),
+        };
+        scope.respond(id, output);
+    }
+}